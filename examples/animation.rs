@@ -0,0 +1,54 @@
+use core::time::Duration;
+
+use ralaire::app::{App, WidgetView};
+use ralaire::view::{button, flex, window};
+use ralaire::widget::CrossAxisAlignment;
+use ralaire::{Animation, AnimationDirection, EasingCurve, Task};
+use winit::error::EventLoopError;
+
+struct Progress {
+    animation: Animation,
+    value: f64,
+    done: bool,
+}
+
+fn app_logic(state: &mut Progress) -> impl WidgetView<Progress> {
+    let status = if state.done {
+        "done!".to_owned()
+    } else {
+        format!("{:.0}%", state.value * 100.)
+    };
+    window(
+        flex((
+            button("start".to_owned()).on_press(|state: &mut Progress| {
+                state.animation =
+                    Animation::new(AnimationDirection::Forward, Duration::from_secs(2))
+                        .with_easing(EasingCurve::EaseOutBounce);
+                state.done = false;
+                Task::animation(
+                    state.animation.clone(),
+                    |state: &mut Progress| state.value = state.animation.value(),
+                    |state: &mut Progress| state.done = true,
+                )
+            }),
+            status,
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        "Animation".to_owned(),
+    )
+}
+
+fn main() -> Result<(), EventLoopError> {
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    App::new(
+        Progress {
+            animation: Animation::new(AnimationDirection::Forward, Duration::from_secs(2)),
+            value: 0.,
+            done: false,
+        },
+        app_logic,
+    )
+    .run()
+}
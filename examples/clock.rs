@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use ralaire::app::{App, WidgetView};
+use ralaire::view::{button, flex, window};
+use ralaire::widget::CrossAxisAlignment;
+use ralaire::Subscription;
+use winit::error::EventLoopError;
+
+struct Stopwatch {
+    running: bool,
+    elapsed: Duration,
+    last_tick: Option<Instant>,
+}
+
+fn app_logic(state: &mut Stopwatch) -> impl WidgetView<Stopwatch> {
+    let label = if state.running { "stop" } else { "start" };
+    window(
+        flex((
+            format!("{:.1}s", state.elapsed.as_secs_f64()),
+            button(label.to_owned()).on_press(|state: &mut Stopwatch| {
+                state.running = !state.running;
+                state.last_tick = None;
+            }),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        "Stopwatch".to_owned(),
+    )
+}
+
+fn subscription(state: &Stopwatch) -> Subscription<Stopwatch> {
+    if !state.running {
+        return Subscription::none();
+    }
+    Subscription::interval(Duration::from_millis(100), |state, now| {
+        if let Some(last_tick) = state.last_tick {
+            state.elapsed += now - last_tick;
+        }
+        state.last_tick = Some(now);
+    })
+}
+
+fn main() -> Result<(), EventLoopError> {
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    App::new(
+        Stopwatch {
+            running: false,
+            elapsed: Duration::ZERO,
+            last_tick: None,
+        },
+        app_logic,
+    )
+    .subscription(subscription)
+    .run()
+}
@@ -1,7 +1,9 @@
 use ralaire::app::{App, WidgetView};
 use ralaire::view::window;
-use ralaire::view::{button, flex};
+use ralaire::view::{button, flex, StyleExt};
 use ralaire::widget::JustifyContent;
+use ralaire::{Task, Theme};
+use vello::peniko::Color;
 use winit::error::EventLoopError;
 
 fn app_logic(state: &mut i32) -> impl WidgetView<i32> {
@@ -11,7 +13,9 @@ fn app_logic(state: &mut i32) -> impl WidgetView<i32> {
             button("increment".to_owned())
                 .on_press(|state| *state += 1)
                 .radius(5.),
-            format!("{}", state),
+            format!("{}", state)
+                .border(1., Color::GRAY)
+                .border_radius(5.),
             button("decrement".to_owned())
                 .on_press(|state| *state -= 1)
                 .radius(5.),
@@ -21,10 +25,19 @@ fn app_logic(state: &mut i32) -> impl WidgetView<i32> {
         .justify_content(JustifyContent::SpaceEvenly),
         "Counter".to_owned(),
     )
+    .header(
+        button("reset".to_owned())
+            .on_press(|state| *state = 0)
+            .radius(5.),
+        format!("Counter: {state}"),
+        button("copy".to_owned())
+            .on_press(|state: &mut i32| Task::<i32>::clipboard_write(state.to_string()))
+            .radius(5.),
+    )
 }
 fn main() -> Result<(), EventLoopError> {
     tracing_subscriber::fmt::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .init();
-    App::new(0, app_logic).run()
+    App::new(0, app_logic).theme(|_| Theme::System).run()
 }
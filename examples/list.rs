@@ -1,5 +1,5 @@
 use ralaire::app::{App, WidgetView};
-use ralaire::view::{button, flex, window};
+use ralaire::view::{button, flex, rich_text, span, text, window};
 use ralaire::widget::FlexDirection;
 use winit::error::EventLoopError;
 
@@ -15,8 +15,19 @@ fn app_logic(state: &mut Vec<String>) -> impl WidgetView<Vec<String>> {
     .direction(FlexDirection::Row);
     println!("number of tasks: {}", state.len());
     let tasks = flex(state.clone());
+    let title = text(format!("{} tasks", state.len()))
+        .size(20.)
+        .bold()
+        .selectable(true);
+    let clear = rich_text([
+        span("Done? "),
+        span("Clear the list")
+            .underline(true)
+            .on_click(|state: &mut Vec<String>| state.clear()),
+    ]);
     window(
-        flex((actions, tasks)).cross_axis_alignment(ralaire::widget::CrossAxisAlignment::Center),
+        flex((title, actions, tasks, clear))
+            .cross_axis_alignment(ralaire::widget::CrossAxisAlignment::Center),
         "List".to_owned(),
     )
 }
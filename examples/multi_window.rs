@@ -0,0 +1,65 @@
+use ralaire::app::{App, WidgetView};
+use ralaire::view::{button, flex, window, AnyWidgetView};
+use ralaire::widget::CrossAxisAlignment;
+use winit::error::EventLoopError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum WindowKey {
+    Main,
+    Inspector(u32),
+}
+
+#[derive(Default)]
+struct Inspectors {
+    count: i32,
+    open: Vec<u32>,
+    next_id: u32,
+}
+
+fn main_window(state: &Inspectors) -> impl WidgetView<Inspectors> {
+    window(
+        flex((
+            format!("count: {}", state.count),
+            format!("{} inspectors open", state.open.len()),
+            button("open inspector".to_owned()).on_press(|state: &mut Inspectors| {
+                state.open.push(state.next_id);
+                state.next_id += 1;
+            }),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        "Main".to_owned(),
+    )
+    .app_id("ralaire.multi_window")
+}
+
+fn inspector(state: &Inspectors, id: u32) -> impl WidgetView<Inspectors> {
+    window(
+        flex((
+            format!("count: {}", state.count),
+            button("increment".to_owned()).on_press(|state: &mut Inspectors| state.count += 1),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        format!("Inspector {id}"),
+    )
+    .app_id("ralaire.multi_window")
+    .inner_size(300., 200.)
+    .resizable(false)
+    // closing an inspector removes its key, which closes the window
+    .on_close(move |state: &mut Inspectors| state.open.retain(|open| *open != id))
+}
+
+fn app_logic(state: &mut Inspectors) -> Vec<(WindowKey, Box<dyn AnyWidgetView<Inspectors>>)> {
+    let mut windows: Vec<(WindowKey, Box<dyn AnyWidgetView<Inspectors>>)> =
+        vec![(WindowKey::Main, Box::new(main_window(state)))];
+    for &id in &state.open {
+        windows.push((WindowKey::Inspector(id), Box::new(inspector(state, id))));
+    }
+    windows
+}
+
+fn main() -> Result<(), EventLoopError> {
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    App::new(Inspectors::default(), app_logic).run()
+}
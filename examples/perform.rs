@@ -0,0 +1,49 @@
+use core::time::Duration;
+
+use ralaire::app::{App, WidgetView};
+use ralaire::view::{button, flex, window};
+use ralaire::widget::CrossAxisAlignment;
+use ralaire::Task;
+use winit::error::EventLoopError;
+
+#[derive(Default)]
+struct Manifest {
+    loading: bool,
+    lines: Option<usize>,
+}
+
+async fn count_lines(path: &str) -> std::io::Result<usize> {
+    // pretend the disk is slow, the window stays responsive while this runs
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let contents = tokio::fs::read_to_string(path).await?;
+    Ok(contents.lines().count())
+}
+
+fn app_logic(state: &mut Manifest) -> impl WidgetView<Manifest> {
+    let status = match (state.loading, state.lines) {
+        (true, _) => "loading...".to_owned(),
+        (false, Some(lines)) => format!("Cargo.toml has {lines} lines"),
+        (false, None) => "nothing loaded".to_owned(),
+    };
+    window(
+        flex((
+            button("load".to_owned()).on_press(|state: &mut Manifest| {
+                state.loading = true;
+                Task::perform(count_lines("Cargo.toml"), |state: &mut Manifest, lines| {
+                    state.loading = false;
+                    state.lines = lines.ok();
+                })
+            }),
+            status,
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        "Perform".to_owned(),
+    )
+}
+
+fn main() -> Result<(), EventLoopError> {
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    App::new(Manifest::default(), app_logic).run()
+}
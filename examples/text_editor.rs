@@ -0,0 +1,25 @@
+use ralaire::app::{App, WidgetView};
+use ralaire::view::{flex, text_editor, window};
+use ralaire::widget::CrossAxisAlignment;
+use winit::error::EventLoopError;
+
+fn app_logic(state: &mut String) -> impl WidgetView<String> {
+    let words = state.split_whitespace().count();
+    window(
+        flex((
+            text_editor(state.clone(), |state: &mut String, notes| *state = notes)
+                .width(400.)
+                .height(300.),
+            format!("{words} words"),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        "Notes".to_owned(),
+    )
+}
+
+fn main() -> Result<(), EventLoopError> {
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    App::new(String::new(), app_logic).run()
+}
@@ -0,0 +1,32 @@
+use ralaire::app::{App, WidgetView};
+use ralaire::view::{flex, text_input, window};
+use ralaire::widget::CrossAxisAlignment;
+use winit::error::EventLoopError;
+
+#[derive(Default)]
+struct Form {
+    name: String,
+    greeting: String,
+}
+
+fn app_logic(state: &mut Form) -> impl WidgetView<Form> {
+    window(
+        flex((
+            text_input(state.name.clone(), |state: &mut Form, name| {
+                state.name = name
+            })
+            .placeholder("Your name".to_owned())
+            .on_submit(|state, name| state.greeting = format!("Hello, {name}!")),
+            state.greeting.clone(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Center),
+        "Text input".to_owned(),
+    )
+}
+
+fn main() -> Result<(), EventLoopError> {
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    App::new(Form::default(), app_logic).run()
+}
@@ -95,6 +95,19 @@ impl Animation {
     pub fn decrement(&mut self) {
         self.value.fetch_sub(1, Ordering::Release);
     }
+    /// Advances the animation by one update interval in its direction
+    pub(crate) fn tick(&mut self) {
+        match self.direction {
+            AnimationDirection::Forward => self.increment(),
+            AnimationDirection::Backward => self.decrement(),
+        }
+    }
+    pub(crate) fn is_finished(&self) -> bool {
+        match self.direction {
+            AnimationDirection::Forward => self.raw_value() >= 1.,
+            AnimationDirection::Backward => self.value.load(Ordering::Acquire) == 0,
+        }
+    }
     pub fn raw_value(&self) -> f64 {
         self.value.load(Ordering::Acquire) as f64 / (self.duration / 16).as_millis() as f64
     }
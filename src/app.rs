@@ -1,32 +1,76 @@
 use winit::window::ResizeDirection;
 
+use crate::clipboard::SystemClipboard;
+use crate::event::mouse::MouseButton;
 use crate::event::window::Event;
 use crate::event::window_event;
-use crate::view::View;
+use crate::task::{InternalTask, RunningAnimation};
+use crate::view::{View, ViewMarker};
 use crate::widget::Widget;
 use crate::window::Window;
 use crate::{event::widget_event_from_window_event, view::RootView};
+use crate::{Clipboard, ClipboardBackend, Subscription, Task, Theme};
+use futures::StreamExt;
 use parley::FontContext;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
 use winit::{
     application::ApplicationHandler,
     error::EventLoopError,
     event::WindowEvent,
-    event_loop::{ActiveEventLoop, EventLoop},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
+    raw_window_handle::HasDisplayHandle,
     window::WindowId,
 };
 
-pub trait WidgetView<State: 'static>: View<State, Element = Self::Widget> {
+pub trait WidgetView<State: 'static>: View<State, Element = Self::Widget> + ViewMarker {
     type Widget: Widget<State>;
 }
 
 impl<State: 'static, W, V> WidgetView<State> for V
 where
-    V: View<State, Element = W>,
+    V: View<State, Element = W> + ViewMarker,
     W: Widget<State>,
 {
     type Widget = W;
 }
 
+/// What the app logic returns: the views of all open windows, each identified by a key.
+/// A single view is shown in a single window, a `Vec<(Key, View)>` opens a window for
+/// every key and closes the windows whose key is gone after a state change. Windows
+/// that show different views can box them as [`AnyWidgetView`](crate::view::AnyWidgetView)
+pub trait Windows<State: 'static> {
+    type Key: PartialEq + Clone + core::fmt::Debug;
+    type View: WidgetView<State>;
+    fn into_windows(self) -> Vec<(Self::Key, Self::View)>;
+}
+
+impl<State: 'static, V> Windows<State> for V
+where
+    V: View<State> + ViewMarker,
+    V::Element: Widget<State>,
+{
+    type Key = ();
+    type View = V;
+    fn into_windows(self) -> Vec<(Self::Key, Self::View)> {
+        vec![((), self)]
+    }
+}
+
+impl<State: 'static, K, V> Windows<State> for Vec<(K, V)>
+where
+    K: PartialEq + Clone + core::fmt::Debug,
+    V: View<State> + ViewMarker,
+    V::Element: Widget<State>,
+{
+    type Key = K;
+    type View = V;
+    fn into_windows(self) -> Vec<(Self::Key, Self::View)> {
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum InternalMessage {
     TitleChanged(String),
@@ -35,89 +79,383 @@ pub enum InternalMessage {
     CloseWindow,
     DragResizeWindow(ResizeDirection),
     DragMoveWindow,
+    /// Opens the window menu of the window manager at the cursor
+    ShowWindowMenu,
+    /// Puts the window behind the other windows
+    LowerWindow,
+    /// A press on empty space of the header bar, ignored if a widget in the header captured it
+    HeaderPressed {
+        button: MouseButton,
+        double_click: bool,
+    },
 }
 
 pub struct App<State, Logic> {
     state: State,
     logic: Logic,
+    subscription: fn(&State) -> Subscription<State>,
+    theme: fn(&State) -> Theme,
+    fonts: Vec<Vec<u8>>,
+    font_families: Option<Vec<String>>,
+    clipboard: Option<Clipboard>,
 }
-impl<'a, State, Logic, V> App<State, Logic>
+impl<'a, State, Logic, W> App<State, Logic>
 where
     State: 'static,
-    Logic: FnMut(&mut State) -> V,
-    V: View<State>,
-    V::Element: Widget<State>,
+    Logic: FnMut(&mut State) -> W,
+    W: Windows<State>,
 {
     pub fn new(state: State, logic: Logic) -> Self {
-        Self { state, logic }
+        Self {
+            state,
+            logic,
+            subscription: |_| Subscription::none(),
+            theme: |_| Theme::default(),
+            fonts: vec![],
+            font_families: None,
+            clipboard: None,
+        }
+    }
+
+    /// Sets the function asked for the app's subscriptions after every state change
+    pub fn subscription(mut self, subscription: fn(&State) -> Subscription<State>) -> Self {
+        self.subscription = subscription;
+        self
+    }
+
+    /// Sets the function asked for the app's theme after every state change
+    pub fn theme(mut self, theme: fn(&State) -> Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Registers a font file (TrueType, OpenType or a collection) next to the installed
+    /// fonts, its families can then be named in font stacks like any installed one
+    pub fn font(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.fonts.push(data.into());
+        self
+    }
+
+    /// Like [`App::font`], with the data read from `path`. A file that can't be read
+    /// is logged and skipped, text then falls back to the next family of its stack
+    pub fn font_file(mut self, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match std::fs::read(path) {
+            Ok(data) => self.fonts.push(data),
+            Err(err) => tracing::error!("Failed to read font {}: {err}", path.display()),
+        }
+        self
+    }
+
+    /// Font stack used by text that doesn't pick its own, replacing the one of the theme.
+    /// Families are tried in order until one of them has the glyphs
+    pub fn font_families(mut self, families: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.font_families = Some(families.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Replaces the system clipboard, for example with a
+    /// [`MemoryClipboard`](crate::MemoryClipboard) in tests and headless runs
+    pub fn clipboard(mut self, backend: impl ClipboardBackend + 'static) -> Self {
+        self.clipboard = Some(Clipboard::new(backend));
+        self
     }
 
     pub fn run(self) -> Result<(), EventLoopError> {
-        InternalApp::run(self.state, self.logic)
+        InternalApp::run(
+            self.state,
+            self.logic,
+            self.subscription,
+            self.theme,
+            self.fonts,
+            self.font_families,
+            self.clipboard,
+        )
     }
 }
 
+/// Messages sent to the event loop from outside of it
+pub enum UserEvent<State> {
+    /// A future spawned by [`Task::perform`] finished, its output gets applied to the state
+    TaskDone(Box<dyn FnOnce(&mut State) + Send>),
+    /// A stream of a running [`Subscription`] produced an item
+    SubscriptionOutput(Box<dyn FnOnce(&mut State) + Send>),
+    /// The settings portal reported the colour scheme preference of the desktop
+    ColorSchemeChanged { prefers_dark: bool },
+}
+
 // pub struct AppContext<State> {
 //     pub state: State,
 //     _event_loop_proxy: EventLoopProxy<()>,
 // }
 // impl<State> AppContext<State> {}
 
-pub struct InternalApp<State, Logic, V>
+pub struct InternalApp<State, Logic, W>
 where
     State: 'static,
-    Logic: FnMut(&mut State) -> V,
-    V: View<State>,
-    // V::Element: Widget<State>,
+    Logic: FnMut(&mut State) -> W,
+    W: Windows<State>,
 {
     state: State,
     logic: Logic,
+    subscription: fn(&State) -> Subscription<State>,
+    theme: fn(&State) -> Theme,
+    /// Whether the system prefers a dark colour scheme, used by [`Theme::System`]
+    prefers_dark: bool,
+    font_families: Option<Vec<String>>,
+    running_subscriptions: HashMap<u64, tokio::task::JoinHandle<()>>,
     runner: tokio::runtime::Runtime,
     font_context: FontContext,
-    windows: Vec<Window<State, V>>,
+    /// The system clipboard is connected when the first window opens, shared by all of them
+    clipboard: Option<Clipboard>,
+    windows: Vec<(W::Key, Window<State, W::View>)>,
+    /// Keys of windows the user closed, they aren't opened again until their key
+    /// is removed from the state and added back
+    closed_windows: Vec<W::Key>,
+    animations: Vec<RunningAnimation<State>>,
+    event_loop_proxy: EventLoopProxy<UserEvent<State>>,
 }
 
-impl<'a, State, Logic, V> InternalApp<State, Logic, V>
+impl<'a, State, Logic, W> InternalApp<State, Logic, W>
 where
     State: 'static,
-    Logic: FnMut(&mut State) -> V,
-    V: View<State>,
-    V::Element: Widget<State>,
+    Logic: FnMut(&mut State) -> W,
+    W: Windows<State>,
 {
-    pub fn run(state: State, logic: Logic) -> Result<(), EventLoopError> {
+    pub fn run(
+        state: State,
+        logic: Logic,
+        subscription: fn(&State) -> Subscription<State>,
+        theme: fn(&State) -> Theme,
+        fonts: Vec<Vec<u8>>,
+        font_families: Option<Vec<String>>,
+        clipboard: Option<Clipboard>,
+    ) -> Result<(), EventLoopError> {
         let runner = tokio::runtime::Runtime::new().unwrap();
+        let mut font_context = FontContext::default();
+        for data in fonts {
+            for (family, _) in font_context.collection.register_fonts(data) {
+                tracing::trace!(
+                    "Registered font family {:?}",
+                    font_context.collection.family_name(family)
+                );
+            }
+        }
         let event_loop = EventLoop::with_user_event().build().unwrap();
-        let _event_loop_proxy = event_loop.create_proxy();
+        let event_loop_proxy = event_loop.create_proxy();
+        let color_scheme_proxy = event_loop_proxy.clone();
+        runner.spawn(async move {
+            let watch = crate::theme::watch_color_scheme(move |prefers_dark| {
+                _ = color_scheme_proxy.send_event(UserEvent::ColorSchemeChanged { prefers_dark });
+            });
+            if let Err(err) = watch.await {
+                tracing::warn!("Can't read the colour scheme from the settings portal: {err}");
+            }
+        });
         let mut app = Self {
             state,
             logic,
+            subscription,
+            theme,
+            prefers_dark: false,
+            font_families,
+            running_subscriptions: HashMap::new(),
             runner,
-            font_context: FontContext::default(),
+            font_context,
+            clipboard,
             windows: vec![],
+            closed_windows: vec![],
+            animations: vec![],
+            event_loop_proxy,
         };
         event_loop.run_app(&mut app)
     }
+
+    fn run_task(&mut self, task: Task<State>, event_loop: &ActiveEventLoop) {
+        for task in task.tasks {
+            match task {
+                InternalTask::Animate {
+                    animation,
+                    tick_callback,
+                    done_callback,
+                } => self.animations.push(RunningAnimation::new(
+                    animation,
+                    tick_callback,
+                    done_callback,
+                )),
+                InternalTask::Perform(future) => {
+                    let event_loop_proxy = self.event_loop_proxy.clone();
+                    self.runner.spawn(async move {
+                        let apply = future.await;
+                        // the event loop is gone if this fails, nothing left to update
+                        _ = event_loop_proxy.send_event(UserEvent::TaskDone(apply));
+                    });
+                }
+                InternalTask::ClipboardWrite(content) => self.clipboard(event_loop).write(content),
+                InternalTask::ClipboardRead { format, callback } => {
+                    let content = self.clipboard(event_loop).read(format);
+                    // applied like a finished future, so the view is rebuilt afterwards
+                    _ = self
+                        .event_loop_proxy
+                        .send_event(UserEvent::TaskDone(Box::new(move |state| {
+                            callback(state, content)
+                        })));
+                }
+            }
+        }
+    }
+
+    /// The clipboard set on the app, or the system one which is connected on first use
+    fn clipboard(&mut self, event_loop: &ActiveEventLoop) -> Clipboard {
+        self.clipboard
+            .get_or_insert_with(|| {
+                let display = event_loop.display_handle().ok();
+                Clipboard::new(SystemClipboard::connect(
+                    display.map(|display| display.as_raw()),
+                ))
+            })
+            .clone()
+    }
+
+    fn set_prefers_dark(&mut self, prefers_dark: bool, event_loop: &ActiveEventLoop) {
+        if prefers_dark != self.prefers_dark {
+            tracing::trace!("System prefers dark: {prefers_dark}");
+            self.prefers_dark = prefers_dark;
+            // the portal can answer before the first windows are opened
+            if !self.windows.is_empty() {
+                self.rebuild_windows(event_loop);
+            }
+        }
+    }
+
+    /// Runs the app logic again and reconciliates every window with its new view,
+    /// opening windows for new keys and closing the ones whose key is gone
+    fn rebuild_windows(&mut self, event_loop: &ActiveEventLoop) {
+        let views = (self.logic)(&mut self.state).into_windows();
+        let mut palette = (self.theme)(&self.state).palette(self.prefers_dark);
+        if let Some(font_families) = &self.font_families {
+            palette.font_families.clone_from(font_families);
+        }
+        self.closed_windows
+            .retain(|closed| views.iter().any(|(key, _)| key == closed));
+        self.windows
+            .retain(|(key, _)| views.iter().any(|(new_key, _)| new_key == key));
+        for (key, view) in views {
+            if self.closed_windows.contains(&key) {
+                continue;
+            }
+            match self.windows.iter_mut().find(|(old_key, _)| *old_key == key) {
+                Some((_, window)) => {
+                    // a new theme lays out and repaints the whole window like any other change
+                    window.palette.clone_from(&palette);
+                    window.reconciliate(view);
+                    window.layout(&mut self.font_context);
+                    window.request_redraw();
+                }
+                None => {
+                    tracing::trace!("Opening window {key:?}");
+                    let clipboard = self.clipboard(event_loop);
+                    let window = self.runner.block_on(Window::new(
+                        event_loop,
+                        RootView::new(view),
+                        format!("{key:?}"),
+                        palette.clone(),
+                        clipboard,
+                    ));
+                    self.windows.push((key, window));
+                }
+            }
+        }
+        if self.windows.is_empty() {
+            event_loop.exit();
+        }
+        self.update_subscriptions();
+    }
+
+    /// Handles the user closing the window at `idx`, either through the window manager
+    /// or the close button. Windows with an `on_close` handler leave it to the app state
+    fn close_window(&mut self, idx: usize, event_loop: &ActiveEventLoop) {
+        match self.windows[idx].1.on_close_requested(&mut self.state) {
+            Some(task) => {
+                self.rebuild_windows(event_loop);
+                self.run_task(task, event_loop);
+            }
+            None => {
+                let (key, window) = self.windows.remove(idx);
+                tracing::trace!("Closing Window={:?}", window.winit_id());
+                self.closed_windows.push(key);
+                if self.windows.is_empty() {
+                    event_loop.exit();
+                }
+            }
+        }
+    }
+
+    /// Starts the subscriptions that are new and cancels the ones that are gone
+    fn update_subscriptions(&mut self) {
+        let subscription = (self.subscription)(&self.state);
+        let ids = subscription
+            .recipes
+            .iter()
+            .map(|recipe| recipe.id)
+            .collect::<Vec<_>>();
+        self.running_subscriptions.retain(|id, handle| {
+            if ids.contains(id) {
+                return true;
+            }
+            handle.abort();
+            false
+        });
+        for recipe in subscription.recipes {
+            if self.running_subscriptions.contains_key(&recipe.id) {
+                continue;
+            }
+            let event_loop_proxy = self.event_loop_proxy.clone();
+            let mut stream = recipe.stream;
+            let handle = self.runner.spawn(async move {
+                while let Some(apply) = stream.next().await {
+                    if event_loop_proxy
+                        .send_event(UserEvent::SubscriptionOutput(apply))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+            self.running_subscriptions.insert(recipe.id, handle);
+        }
+    }
 }
 
-impl<'a, State, Logic, V> ApplicationHandler<()> for InternalApp<State, Logic, V>
+impl<'a, State, Logic, W> ApplicationHandler<UserEvent<State>> for InternalApp<State, Logic, W>
 where
     State: 'static,
-    Logic: FnMut(&mut State) -> V,
-
-    V: View<State>,
-    V::Element: Widget<State>,
+    Logic: FnMut(&mut State) -> W,
+    W: Windows<State>,
 {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         tracing::trace!("Resumed the event loop");
-        let view = (self.logic)(&mut self.state);
-        let root_view = RootView::new(view);
-        let w = self
-            .runner
-            .block_on(Window::new(event_loop, root_view, "TODO: id?".to_owned()));
-        self.windows.push(w);
+        // Wayland leaves it to the settings portal
+        if let Some(theme) = event_loop.system_theme() {
+            self.prefers_dark = theme == winit::window::Theme::Dark;
+        }
+        self.rebuild_windows(event_loop);
     }
-    fn user_event(&mut self, _event_loop: &ActiveEventLoop, message: ()) {
-        tracing::trace!("User message: {message:?}");
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: UserEvent<State>) {
+        match event {
+            UserEvent::TaskDone(apply) => {
+                tracing::trace!("Task finished");
+                apply(&mut self.state);
+                self.rebuild_windows(event_loop);
+            }
+            UserEvent::SubscriptionOutput(apply) => {
+                apply(&mut self.state);
+                self.rebuild_windows(event_loop);
+            }
+            UserEvent::ColorSchemeChanged { prefers_dark } => {
+                self.set_prefers_dark(prefers_dark, event_loop)
+            }
+        }
     }
     fn window_event(
         &mut self,
@@ -130,66 +468,90 @@ where
             .windows
             .iter_mut()
             .enumerate()
-            .find(|(_, window)| window.winit_id() == window_id)
+            .find(|(_, (_, window))| window.winit_id() == window_id)
         {
-            Some((idx, window)) => (idx, window),
+            Some((idx, (_, window))) => (idx, window),
             None => return,
         };
-        let event = window_event(&event, window.cursor_pos(), window.scale_factor());
+        let event = window_event(
+            &event,
+            window.cursor_pos(),
+            window.scale_factor(),
+            window.modifiers(),
+        );
         let event = match event {
             Some(event) => event,
             None => return,
         };
 
         match event {
-            Event::CloseRequested => {
-                tracing::trace!("Closing Window={:?}", window.winit_id());
-                if self.windows.len() == 1 {
-                    self.windows.pop();
-                    event_loop.exit()
-                } else {
-                    self.windows.remove(idx);
-                }
-            }
+            Event::CloseRequested => self.close_window(idx, event_loop),
             Event::Resized(size) => {
                 tracing::trace!("{:?}", size);
                 window.resize(size, &mut self.font_context);
             }
             Event::ScaleFactorChanged(scale_factor) => window.scale_factor = scale_factor,
+            Event::ThemeChanged { prefers_dark } => self.set_prefers_dark(prefers_dark, event_loop),
             Event::RedrawRequested => {
                 window.paint();
             }
-            Event::Keyboard(_) | Event::Mouse(_) | Event::Touch(_) => {
+            Event::Keyboard(_) | Event::Ime(_) | Event::Mouse(_) | Event::Touch(_) => {
                 let mut should_close = false;
                 let mut state_changed = false;
 
-                window.widget_event(
+                let task = window.widget_event(
                     widget_event_from_window_event(event).unwrap(),
                     &mut self.state,
+                    &mut self.font_context,
                     &mut should_close,
                     &mut state_changed,
                 );
                 if state_changed {
-                    // TODO: Reconciliate window list properly
                     tracing::trace!("state changed");
-                    let view = (self.logic)(&mut self.state);
-                    window.reconciliate(view);
-                    window.layout(&mut self.font_context);
-                    window.request_redraw();
+                    self.rebuild_windows(event_loop);
                 }
+                self.run_task(task, event_loop);
 
                 if should_close {
-                    if self.windows.len() == 1 {
-                        self.windows.pop();
-                        event_loop.exit()
-                    } else {
-                        self.windows.remove(idx);
+                    // the window might be gone or moved after rebuilding
+                    if let Some(idx) = self
+                        .windows
+                        .iter()
+                        .position(|(_, window)| window.winit_id() == window_id)
+                    {
+                        self.close_window(idx, event_loop);
                     }
                 }
             }
         }
     }
-    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {}
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let now = Instant::now();
+        let mut state_changed = false;
+        self.animations.retain_mut(|animation| {
+            if animation.next_tick() > now {
+                return true;
+            }
+            state_changed = true;
+            animation.tick(&mut self.state)
+        });
+        if state_changed {
+            self.rebuild_windows(event_loop);
+        }
+        match self
+            .animations
+            .iter()
+            .map(|animation| animation.next_tick())
+            .min()
+        {
+            Some(next_tick) => event_loop.set_control_flow(ControlFlow::WaitUntil(next_tick)),
+            None => event_loop.set_control_flow(ControlFlow::Wait),
+        }
+    }
 
-    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {}
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        // the clipboard has to go before the display connection it was made from
+        self.windows.clear();
+        self.clipboard = None;
+    }
 }
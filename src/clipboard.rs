@@ -0,0 +1,225 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use winit::raw_window_handle::RawDisplayHandle;
+
+/// Image data in RGBA8, row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardImage {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardContent {
+    Text(String),
+    Image(ClipboardImage),
+}
+
+impl From<String> for ClipboardContent {
+    fn from(text: String) -> Self {
+        ClipboardContent::Text(text)
+    }
+}
+
+impl From<&str> for ClipboardContent {
+    fn from(text: &str) -> Self {
+        ClipboardContent::Text(text.to_owned())
+    }
+}
+
+impl From<ClipboardImage> for ClipboardContent {
+    fn from(image: ClipboardImage) -> Self {
+        ClipboardContent::Image(image)
+    }
+}
+
+/// Kind of content to read from the clipboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipboardFormat {
+    Text,
+    Image,
+}
+
+/// Where the [`Clipboard`] stores its content, the system clipboard by default.
+/// Set another one with [`App::clipboard`](crate::app::App::clipboard)
+pub trait ClipboardBackend {
+    fn write(&self, content: ClipboardContent);
+    /// `None` if the clipboard is empty or holds content of another format
+    fn read(&self, format: ClipboardFormat) -> Option<ClipboardContent>;
+}
+
+/// Clipboard shared by every window of the app, reachable from widgets through
+/// [`EventContext::clipboard`](crate::event::EventContext::clipboard) and from the
+/// app logic through [`Task::clipboard_write`](crate::Task::clipboard_write) and
+/// [`Task::clipboard_read`](crate::Task::clipboard_read)
+#[derive(Clone)]
+pub struct Clipboard {
+    backend: Rc<dyn ClipboardBackend>,
+}
+
+impl Clipboard {
+    pub fn new(backend: impl ClipboardBackend + 'static) -> Self {
+        Self {
+            backend: Rc::new(backend),
+        }
+    }
+    pub fn write(&self, content: impl Into<ClipboardContent>) {
+        self.backend.write(content.into());
+    }
+    pub fn write_text(&self, text: impl Into<String>) {
+        self.write(ClipboardContent::Text(text.into()));
+    }
+    pub fn write_image(&self, image: ClipboardImage) {
+        self.write(ClipboardContent::Image(image));
+    }
+    pub fn read(&self, format: ClipboardFormat) -> Option<ClipboardContent> {
+        self.backend.read(format)
+    }
+    pub fn read_text(&self) -> Option<String> {
+        match self.read(ClipboardFormat::Text) {
+            Some(ClipboardContent::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+    pub fn read_image(&self) -> Option<ClipboardImage> {
+        match self.read(ClipboardFormat::Image) {
+            Some(ClipboardContent::Image(image)) => Some(image),
+            _ => None,
+        }
+    }
+}
+
+/// Keeps the content in memory, for tests and apps without a display.
+/// Clones share the same content
+#[derive(Debug, Clone, Default)]
+pub struct MemoryClipboard {
+    content: Rc<RefCell<Option<ClipboardContent>>>,
+}
+
+impl ClipboardBackend for MemoryClipboard {
+    fn write(&self, content: ClipboardContent) {
+        *self.content.borrow_mut() = Some(content);
+    }
+    fn read(&self, format: ClipboardFormat) -> Option<ClipboardContent> {
+        let content = self.content.borrow();
+        match (&*content, format) {
+            (Some(ClipboardContent::Text(_)), ClipboardFormat::Text)
+            | (Some(ClipboardContent::Image(_)), ClipboardFormat::Image) => content.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// Clipboard of the system, winit doesn't offer one so it talks to the Wayland
+/// compositor directly. Elsewhere writes are dropped and reads find nothing
+pub(crate) struct SystemClipboard {
+    wayland: Option<smithay_clipboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    pub fn connect(display: Option<RawDisplayHandle>) -> Self {
+        let wayland = match display {
+            // SAFETY: the app drops the clipboard when the event loop exits, while the
+            // display connection is still alive
+            Some(RawDisplayHandle::Wayland(handle)) => {
+                Some(unsafe { smithay_clipboard::Clipboard::new(handle.display.as_ptr()) })
+            }
+            _ => {
+                tracing::warn!("No clipboard available on this platform");
+                None
+            }
+        };
+        Self { wayland }
+    }
+}
+
+impl ClipboardBackend for SystemClipboard {
+    fn write(&self, content: ClipboardContent) {
+        let Some(clipboard) = &self.wayland else {
+            return;
+        };
+        match content {
+            ClipboardContent::Text(text) => clipboard.store(text),
+            // smithay-clipboard only offers text mime types
+            ClipboardContent::Image(_) => {
+                tracing::warn!("Copying images to the system clipboard isn't supported yet")
+            }
+        }
+    }
+    fn read(&self, format: ClipboardFormat) -> Option<ClipboardContent> {
+        let clipboard = self.wayland.as_ref()?;
+        match format {
+            ClipboardFormat::Text => match clipboard.load() {
+                Ok(text) => Some(ClipboardContent::Text(text)),
+                Err(err) => {
+                    tracing::debug!("Failed to read the clipboard: {err}");
+                    None
+                }
+            },
+            ClipboardFormat::Image => {
+                tracing::warn!("Pasting images from the system clipboard isn't supported yet");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> ClipboardImage {
+        ClipboardImage {
+            rgba: vec![255, 0, 0, 255, 0, 0, 255, 128],
+            width: 2,
+            height: 1,
+        }
+    }
+
+    #[test]
+    fn memory_clipboard_round_trips_text() {
+        let clipboard = Clipboard::new(MemoryClipboard::default());
+        clipboard.write_text("héllo");
+        assert_eq!(clipboard.read_text().as_deref(), Some("héllo"));
+        clipboard.write("again");
+        assert_eq!(
+            clipboard.read(ClipboardFormat::Text),
+            Some(ClipboardContent::Text("again".to_owned()))
+        );
+    }
+
+    #[test]
+    fn memory_clipboard_round_trips_images() {
+        let clipboard = Clipboard::new(MemoryClipboard::default());
+        clipboard.write_image(image());
+        assert_eq!(clipboard.read_image(), Some(image()));
+        assert_eq!(
+            clipboard.read(ClipboardFormat::Image),
+            Some(ClipboardContent::Image(image()))
+        );
+    }
+
+    #[test]
+    fn memory_clipboard_format_mismatch_reads_none() {
+        let clipboard = Clipboard::new(MemoryClipboard::default());
+        assert_eq!(clipboard.read_text(), None);
+        assert_eq!(clipboard.read_image(), None);
+        clipboard.write_text("text");
+        assert_eq!(clipboard.read_image(), None);
+        clipboard.write_image(image());
+        assert_eq!(clipboard.read_text(), None);
+    }
+
+    #[test]
+    fn memory_clipboard_clones_share_content() {
+        let backend = MemoryClipboard::default();
+        let clipboard = Clipboard::new(backend.clone());
+        clipboard.write_text("shared");
+        assert_eq!(
+            backend.read(ClipboardFormat::Text),
+            Some(ClipboardContent::Text("shared".to_owned()))
+        );
+    }
+}
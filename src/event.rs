@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::sync::Arc;
 use std::vec::Drain;
 
@@ -5,11 +6,15 @@ use vello::peniko::kurbo::Point;
 use vello::peniko::kurbo::Vec2;
 use winit::window::CursorIcon;
 use winit::window::Window as WinitWindow;
+pub mod ime;
 pub mod keyboard;
 pub mod mouse;
 pub mod touch;
 pub mod window;
+use crate::clipboard::Clipboard;
+use crate::widget::WidgetIdPath;
 use crate::InternalMessage;
+use crate::Task;
 #[derive(Debug, Clone, PartialEq)]
 pub enum Status {
     Ignored,
@@ -17,19 +22,31 @@ pub enum Status {
 }
 pub struct EventContext {
     pub repaint_needed: bool,
+    /// Set by widgets whose size or text layout changed without a change to the app state
+    pub layout_needed: bool,
     pub state_changed: bool,
     pub winit_window: Arc<WinitWindow>,
     internal_messages: Vec<InternalMessage>,
     cursor: CursorIcon,
+    focus_requested: bool,
+    requested_focus: Option<WidgetIdPath>,
+    // type erased `Task<State>`s, EventContext isn't generic over the app state
+    tasks: Vec<Box<dyn Any>>,
+    clipboard: Clipboard,
 }
 impl EventContext {
-    pub fn new(winit_window: Arc<WinitWindow>) -> Self {
+    pub(crate) fn new(winit_window: Arc<WinitWindow>, clipboard: Clipboard) -> Self {
         EventContext {
             repaint_needed: false,
+            layout_needed: false,
             state_changed: false,
             winit_window,
             internal_messages: vec![],
             cursor: CursorIcon::Default,
+            focus_requested: false,
+            requested_focus: None,
+            tasks: vec![],
+            clipboard,
         }
     }
 
@@ -48,11 +65,48 @@ impl EventContext {
     pub fn cursor(&self) -> CursorIcon {
         self.cursor
     }
+
+    /// Asks the window to give keyboard focus to the widget currently handling the event
+    pub fn request_focus(&mut self) {
+        self.focus_requested = true;
+    }
+
+    /// Records `id_path` as the new focus target if the widget at `id_path` just requested focus
+    pub(crate) fn resolve_focus_request(&mut self, id_path: &WidgetIdPath) {
+        if self.focus_requested {
+            self.focus_requested = false;
+            self.requested_focus = Some(id_path.clone());
+        }
+    }
+
+    pub(crate) fn take_requested_focus(&mut self) -> Option<WidgetIdPath> {
+        self.requested_focus.take()
+    }
+
+    /// Hands a task returned by an event handler to the app, which runs it after the event
+    pub fn push_task<State: 'static>(&mut self, task: Task<State>) {
+        if !task.tasks.is_empty() {
+            self.tasks.push(Box::new(task));
+        }
+    }
+
+    /// The clipboard of the app, shared by all windows
+    pub fn clipboard(&self) -> &Clipboard {
+        &self.clipboard
+    }
+
+    pub(crate) fn take_tasks<State: 'static>(&mut self) -> Task<State> {
+        self.tasks
+            .drain(..)
+            .filter_map(|task| task.downcast::<Task<State>>().ok())
+            .fold(Task::none(), |task, mut other| task.append(&mut other))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum WidgetEvent {
     Keyboard(keyboard::Event),
+    Ime(ime::Event),
     Mouse(mouse::Event),
     Touch(touch::Event),
 }
@@ -63,7 +117,9 @@ pub fn widget_event_from_window_event(event: window::Event) -> Option<WidgetEven
         window::Event::CloseRequested => None,
         window::Event::ScaleFactorChanged(_) => None,
         window::Event::RedrawRequested => None,
+        window::Event::ThemeChanged { .. } => None,
         window::Event::Keyboard(keyboard_event) => Some(WidgetEvent::Keyboard(keyboard_event)),
+        window::Event::Ime(ime_event) => Some(WidgetEvent::Ime(ime_event)),
         window::Event::Mouse(mut mouse_event) => {
             match mouse_event {
                 mouse::Event::Move { position } => {
@@ -98,6 +154,7 @@ pub fn widget_event_from_window_event(event: window::Event) -> Option<WidgetEven
 pub fn widget_event(event: WidgetEvent, widget_position: Point) -> WidgetEvent {
     match event {
         WidgetEvent::Keyboard(keyboard_event) => WidgetEvent::Keyboard(keyboard_event),
+        WidgetEvent::Ime(ime_event) => WidgetEvent::Ime(ime_event),
         WidgetEvent::Mouse(mut mouse_event) => {
             match mouse_event {
                 mouse::Event::Move { position } => {
@@ -148,6 +205,7 @@ pub fn window_event(
     event: &winit::event::WindowEvent,
     cursor_position: Point,
     scale_factor: f64,
+    modifiers: keyboard::Modifiers,
 ) -> Option<window::Event> {
     use winit::event::WindowEvent;
 
@@ -158,6 +216,9 @@ pub fn window_event(
             Some(window::Event::ScaleFactorChanged(*scale_factor))
         }
         WindowEvent::CloseRequested => Some(window::Event::CloseRequested),
+        WindowEvent::ThemeChanged(theme) => Some(window::Event::ThemeChanged {
+            prefers_dark: *theme == winit::window::Theme::Dark,
+        }),
         WindowEvent::CursorMoved { position, .. } => {
             Some(window::Event::Mouse(mouse::Event::Move {
                 position: Point::new(position.x / scale_factor, position.y / scale_factor),
@@ -197,6 +258,338 @@ pub fn window_event(
                 }))
             }
         },
+        WindowEvent::KeyboardInput { event, .. } => {
+            let key = key(&event.logical_key);
+            let physical_key = key_code(event.physical_key);
+            Some(window::Event::Keyboard(match event.state {
+                winit::event::ElementState::Pressed => keyboard::Event::KeyPressed {
+                    key,
+                    physical_key,
+                    text: event.text.as_ref().map(|text| text.to_string()),
+                    repeat: event.repeat,
+                    modifiers,
+                },
+                winit::event::ElementState::Released => keyboard::Event::KeyReleased {
+                    key,
+                    physical_key,
+                    modifiers,
+                },
+            }))
+        }
+        WindowEvent::ModifiersChanged(new_modifiers) => Some(window::Event::Keyboard(
+            keyboard::Event::ModifiersChanged(self::modifiers(new_modifiers.state())),
+        )),
+        WindowEvent::Ime(ime) => Some(window::Event::Ime(match ime {
+            winit::event::Ime::Enabled => ime::Event::Enabled,
+            winit::event::Ime::Preedit(text, cursor) => ime::Event::Preedit {
+                text: text.clone(),
+                cursor: *cursor,
+            },
+            winit::event::Ime::Commit(text) => ime::Event::Commit(text.clone()),
+            winit::event::Ime::Disabled => ime::Event::Disabled,
+        })),
         _ => None,
     }
 }
+
+/// Converts a winit key into an ralaire logical key.
+pub fn key(key: &winit::keyboard::Key) -> keyboard::Key {
+    use keyboard::NamedKey;
+    use winit::keyboard::NamedKey as WinitNamedKey;
+
+    match key {
+        winit::keyboard::Key::Character(c) => keyboard::Key::Character(c.to_string()),
+        winit::keyboard::Key::Named(named_key) => match named_key {
+            WinitNamedKey::Alt => keyboard::Key::Named(NamedKey::Alt),
+            WinitNamedKey::AltGraph => keyboard::Key::Named(NamedKey::AltGraph),
+            WinitNamedKey::CapsLock => keyboard::Key::Named(NamedKey::CapsLock),
+            WinitNamedKey::Control => keyboard::Key::Named(NamedKey::Control),
+            WinitNamedKey::Fn => keyboard::Key::Named(NamedKey::Fn),
+            WinitNamedKey::FnLock => keyboard::Key::Named(NamedKey::FnLock),
+            WinitNamedKey::NumLock => keyboard::Key::Named(NamedKey::NumLock),
+            WinitNamedKey::ScrollLock => keyboard::Key::Named(NamedKey::ScrollLock),
+            WinitNamedKey::Shift => keyboard::Key::Named(NamedKey::Shift),
+            WinitNamedKey::Symbol => keyboard::Key::Named(NamedKey::Symbol),
+            WinitNamedKey::SymbolLock => keyboard::Key::Named(NamedKey::SymbolLock),
+            WinitNamedKey::Meta => keyboard::Key::Named(NamedKey::Meta),
+            WinitNamedKey::Hyper => keyboard::Key::Named(NamedKey::Hyper),
+            WinitNamedKey::Super => keyboard::Key::Named(NamedKey::Super),
+            WinitNamedKey::Enter => keyboard::Key::Named(NamedKey::Enter),
+            WinitNamedKey::Tab => keyboard::Key::Named(NamedKey::Tab),
+            WinitNamedKey::Space => keyboard::Key::Named(NamedKey::Space),
+            WinitNamedKey::ArrowDown => keyboard::Key::Named(NamedKey::ArrowDown),
+            WinitNamedKey::ArrowLeft => keyboard::Key::Named(NamedKey::ArrowLeft),
+            WinitNamedKey::ArrowRight => keyboard::Key::Named(NamedKey::ArrowRight),
+            WinitNamedKey::ArrowUp => keyboard::Key::Named(NamedKey::ArrowUp),
+            WinitNamedKey::End => keyboard::Key::Named(NamedKey::End),
+            WinitNamedKey::Home => keyboard::Key::Named(NamedKey::Home),
+            WinitNamedKey::PageDown => keyboard::Key::Named(NamedKey::PageDown),
+            WinitNamedKey::PageUp => keyboard::Key::Named(NamedKey::PageUp),
+            WinitNamedKey::Backspace => keyboard::Key::Named(NamedKey::Backspace),
+            WinitNamedKey::Clear => keyboard::Key::Named(NamedKey::Clear),
+            WinitNamedKey::Copy => keyboard::Key::Named(NamedKey::Copy),
+            WinitNamedKey::Cut => keyboard::Key::Named(NamedKey::Cut),
+            WinitNamedKey::Delete => keyboard::Key::Named(NamedKey::Delete),
+            WinitNamedKey::Insert => keyboard::Key::Named(NamedKey::Insert),
+            WinitNamedKey::Paste => keyboard::Key::Named(NamedKey::Paste),
+            WinitNamedKey::Redo => keyboard::Key::Named(NamedKey::Redo),
+            WinitNamedKey::Undo => keyboard::Key::Named(NamedKey::Undo),
+            WinitNamedKey::ContextMenu => keyboard::Key::Named(NamedKey::ContextMenu),
+            WinitNamedKey::Escape => keyboard::Key::Named(NamedKey::Escape),
+            WinitNamedKey::Find => keyboard::Key::Named(NamedKey::Find),
+            WinitNamedKey::Help => keyboard::Key::Named(NamedKey::Help),
+            WinitNamedKey::Pause => keyboard::Key::Named(NamedKey::Pause),
+            WinitNamedKey::Play => keyboard::Key::Named(NamedKey::Play),
+            WinitNamedKey::Select => keyboard::Key::Named(NamedKey::Select),
+            WinitNamedKey::ZoomIn => keyboard::Key::Named(NamedKey::ZoomIn),
+            WinitNamedKey::ZoomOut => keyboard::Key::Named(NamedKey::ZoomOut),
+            WinitNamedKey::PrintScreen => keyboard::Key::Named(NamedKey::PrintScreen),
+            WinitNamedKey::F1 => keyboard::Key::Named(NamedKey::F1),
+            WinitNamedKey::F2 => keyboard::Key::Named(NamedKey::F2),
+            WinitNamedKey::F3 => keyboard::Key::Named(NamedKey::F3),
+            WinitNamedKey::F4 => keyboard::Key::Named(NamedKey::F4),
+            WinitNamedKey::F5 => keyboard::Key::Named(NamedKey::F5),
+            WinitNamedKey::F6 => keyboard::Key::Named(NamedKey::F6),
+            WinitNamedKey::F7 => keyboard::Key::Named(NamedKey::F7),
+            WinitNamedKey::F8 => keyboard::Key::Named(NamedKey::F8),
+            WinitNamedKey::F9 => keyboard::Key::Named(NamedKey::F9),
+            WinitNamedKey::F10 => keyboard::Key::Named(NamedKey::F10),
+            WinitNamedKey::F11 => keyboard::Key::Named(NamedKey::F11),
+            WinitNamedKey::F12 => keyboard::Key::Named(NamedKey::F12),
+            WinitNamedKey::F13 => keyboard::Key::Named(NamedKey::F13),
+            WinitNamedKey::F14 => keyboard::Key::Named(NamedKey::F14),
+            WinitNamedKey::F15 => keyboard::Key::Named(NamedKey::F15),
+            WinitNamedKey::F16 => keyboard::Key::Named(NamedKey::F16),
+            WinitNamedKey::F17 => keyboard::Key::Named(NamedKey::F17),
+            WinitNamedKey::F18 => keyboard::Key::Named(NamedKey::F18),
+            WinitNamedKey::F19 => keyboard::Key::Named(NamedKey::F19),
+            WinitNamedKey::F20 => keyboard::Key::Named(NamedKey::F20),
+            WinitNamedKey::F21 => keyboard::Key::Named(NamedKey::F21),
+            WinitNamedKey::F22 => keyboard::Key::Named(NamedKey::F22),
+            WinitNamedKey::F23 => keyboard::Key::Named(NamedKey::F23),
+            WinitNamedKey::F24 => keyboard::Key::Named(NamedKey::F24),
+            _ => keyboard::Key::Unidentified,
+        },
+        _ => keyboard::Key::Unidentified,
+    }
+}
+
+/// Converts a winit physical key into an ralaire key code.
+pub fn key_code(physical_key: winit::keyboard::PhysicalKey) -> keyboard::KeyCode {
+    use keyboard::KeyCode;
+    use winit::keyboard::KeyCode as WinitKeyCode;
+
+    let code = match physical_key {
+        winit::keyboard::PhysicalKey::Code(code) => code,
+        winit::keyboard::PhysicalKey::Unidentified(_) => return KeyCode::Unidentified,
+    };
+    match code {
+        WinitKeyCode::Backquote => KeyCode::Backquote,
+        WinitKeyCode::Backslash => KeyCode::Backslash,
+        WinitKeyCode::BracketLeft => KeyCode::BracketLeft,
+        WinitKeyCode::BracketRight => KeyCode::BracketRight,
+        WinitKeyCode::Comma => KeyCode::Comma,
+        WinitKeyCode::Digit0 => KeyCode::Digit0,
+        WinitKeyCode::Digit1 => KeyCode::Digit1,
+        WinitKeyCode::Digit2 => KeyCode::Digit2,
+        WinitKeyCode::Digit3 => KeyCode::Digit3,
+        WinitKeyCode::Digit4 => KeyCode::Digit4,
+        WinitKeyCode::Digit5 => KeyCode::Digit5,
+        WinitKeyCode::Digit6 => KeyCode::Digit6,
+        WinitKeyCode::Digit7 => KeyCode::Digit7,
+        WinitKeyCode::Digit8 => KeyCode::Digit8,
+        WinitKeyCode::Digit9 => KeyCode::Digit9,
+        WinitKeyCode::Equal => KeyCode::Equal,
+        WinitKeyCode::IntlBackslash => KeyCode::IntlBackslash,
+        WinitKeyCode::IntlRo => KeyCode::IntlRo,
+        WinitKeyCode::IntlYen => KeyCode::IntlYen,
+        WinitKeyCode::KeyA => KeyCode::KeyA,
+        WinitKeyCode::KeyB => KeyCode::KeyB,
+        WinitKeyCode::KeyC => KeyCode::KeyC,
+        WinitKeyCode::KeyD => KeyCode::KeyD,
+        WinitKeyCode::KeyE => KeyCode::KeyE,
+        WinitKeyCode::KeyF => KeyCode::KeyF,
+        WinitKeyCode::KeyG => KeyCode::KeyG,
+        WinitKeyCode::KeyH => KeyCode::KeyH,
+        WinitKeyCode::KeyI => KeyCode::KeyI,
+        WinitKeyCode::KeyJ => KeyCode::KeyJ,
+        WinitKeyCode::KeyK => KeyCode::KeyK,
+        WinitKeyCode::KeyL => KeyCode::KeyL,
+        WinitKeyCode::KeyM => KeyCode::KeyM,
+        WinitKeyCode::KeyN => KeyCode::KeyN,
+        WinitKeyCode::KeyO => KeyCode::KeyO,
+        WinitKeyCode::KeyP => KeyCode::KeyP,
+        WinitKeyCode::KeyQ => KeyCode::KeyQ,
+        WinitKeyCode::KeyR => KeyCode::KeyR,
+        WinitKeyCode::KeyS => KeyCode::KeyS,
+        WinitKeyCode::KeyT => KeyCode::KeyT,
+        WinitKeyCode::KeyU => KeyCode::KeyU,
+        WinitKeyCode::KeyV => KeyCode::KeyV,
+        WinitKeyCode::KeyW => KeyCode::KeyW,
+        WinitKeyCode::KeyX => KeyCode::KeyX,
+        WinitKeyCode::KeyY => KeyCode::KeyY,
+        WinitKeyCode::KeyZ => KeyCode::KeyZ,
+        WinitKeyCode::Minus => KeyCode::Minus,
+        WinitKeyCode::Period => KeyCode::Period,
+        WinitKeyCode::Quote => KeyCode::Quote,
+        WinitKeyCode::Semicolon => KeyCode::Semicolon,
+        WinitKeyCode::Slash => KeyCode::Slash,
+        WinitKeyCode::AltLeft => KeyCode::AltLeft,
+        WinitKeyCode::AltRight => KeyCode::AltRight,
+        WinitKeyCode::Backspace => KeyCode::Backspace,
+        WinitKeyCode::CapsLock => KeyCode::CapsLock,
+        WinitKeyCode::ContextMenu => KeyCode::ContextMenu,
+        WinitKeyCode::ControlLeft => KeyCode::ControlLeft,
+        WinitKeyCode::ControlRight => KeyCode::ControlRight,
+        WinitKeyCode::Enter => KeyCode::Enter,
+        WinitKeyCode::SuperLeft => KeyCode::SuperLeft,
+        WinitKeyCode::SuperRight => KeyCode::SuperRight,
+        WinitKeyCode::ShiftLeft => KeyCode::ShiftLeft,
+        WinitKeyCode::ShiftRight => KeyCode::ShiftRight,
+        WinitKeyCode::Space => KeyCode::Space,
+        WinitKeyCode::Tab => KeyCode::Tab,
+        WinitKeyCode::Convert => KeyCode::Convert,
+        WinitKeyCode::KanaMode => KeyCode::KanaMode,
+        WinitKeyCode::Lang1 => KeyCode::Lang1,
+        WinitKeyCode::Lang2 => KeyCode::Lang2,
+        WinitKeyCode::Lang3 => KeyCode::Lang3,
+        WinitKeyCode::Lang4 => KeyCode::Lang4,
+        WinitKeyCode::Lang5 => KeyCode::Lang5,
+        WinitKeyCode::NonConvert => KeyCode::NonConvert,
+        WinitKeyCode::Delete => KeyCode::Delete,
+        WinitKeyCode::End => KeyCode::End,
+        WinitKeyCode::Help => KeyCode::Help,
+        WinitKeyCode::Home => KeyCode::Home,
+        WinitKeyCode::Insert => KeyCode::Insert,
+        WinitKeyCode::PageDown => KeyCode::PageDown,
+        WinitKeyCode::PageUp => KeyCode::PageUp,
+        WinitKeyCode::ArrowDown => KeyCode::ArrowDown,
+        WinitKeyCode::ArrowLeft => KeyCode::ArrowLeft,
+        WinitKeyCode::ArrowRight => KeyCode::ArrowRight,
+        WinitKeyCode::ArrowUp => KeyCode::ArrowUp,
+        WinitKeyCode::NumLock => KeyCode::NumLock,
+        WinitKeyCode::Numpad0 => KeyCode::Numpad0,
+        WinitKeyCode::Numpad1 => KeyCode::Numpad1,
+        WinitKeyCode::Numpad2 => KeyCode::Numpad2,
+        WinitKeyCode::Numpad3 => KeyCode::Numpad3,
+        WinitKeyCode::Numpad4 => KeyCode::Numpad4,
+        WinitKeyCode::Numpad5 => KeyCode::Numpad5,
+        WinitKeyCode::Numpad6 => KeyCode::Numpad6,
+        WinitKeyCode::Numpad7 => KeyCode::Numpad7,
+        WinitKeyCode::Numpad8 => KeyCode::Numpad8,
+        WinitKeyCode::Numpad9 => KeyCode::Numpad9,
+        WinitKeyCode::NumpadAdd => KeyCode::NumpadAdd,
+        WinitKeyCode::NumpadBackspace => KeyCode::NumpadBackspace,
+        WinitKeyCode::NumpadClear => KeyCode::NumpadClear,
+        WinitKeyCode::NumpadClearEntry => KeyCode::NumpadClearEntry,
+        WinitKeyCode::NumpadComma => KeyCode::NumpadComma,
+        WinitKeyCode::NumpadDecimal => KeyCode::NumpadDecimal,
+        WinitKeyCode::NumpadDivide => KeyCode::NumpadDivide,
+        WinitKeyCode::NumpadEnter => KeyCode::NumpadEnter,
+        WinitKeyCode::NumpadEqual => KeyCode::NumpadEqual,
+        WinitKeyCode::NumpadHash => KeyCode::NumpadHash,
+        WinitKeyCode::NumpadMemoryAdd => KeyCode::NumpadMemoryAdd,
+        WinitKeyCode::NumpadMemoryClear => KeyCode::NumpadMemoryClear,
+        WinitKeyCode::NumpadMemoryRecall => KeyCode::NumpadMemoryRecall,
+        WinitKeyCode::NumpadMemoryStore => KeyCode::NumpadMemoryStore,
+        WinitKeyCode::NumpadMemorySubtract => KeyCode::NumpadMemorySubtract,
+        WinitKeyCode::NumpadMultiply => KeyCode::NumpadMultiply,
+        WinitKeyCode::NumpadParenLeft => KeyCode::NumpadParenLeft,
+        WinitKeyCode::NumpadParenRight => KeyCode::NumpadParenRight,
+        WinitKeyCode::NumpadStar => KeyCode::NumpadStar,
+        WinitKeyCode::NumpadSubtract => KeyCode::NumpadSubtract,
+        WinitKeyCode::Escape => KeyCode::Escape,
+        WinitKeyCode::Fn => KeyCode::Fn,
+        WinitKeyCode::FnLock => KeyCode::FnLock,
+        WinitKeyCode::PrintScreen => KeyCode::PrintScreen,
+        WinitKeyCode::ScrollLock => KeyCode::ScrollLock,
+        WinitKeyCode::Pause => KeyCode::Pause,
+        WinitKeyCode::BrowserBack => KeyCode::BrowserBack,
+        WinitKeyCode::BrowserFavorites => KeyCode::BrowserFavorites,
+        WinitKeyCode::BrowserForward => KeyCode::BrowserForward,
+        WinitKeyCode::BrowserHome => KeyCode::BrowserHome,
+        WinitKeyCode::BrowserRefresh => KeyCode::BrowserRefresh,
+        WinitKeyCode::BrowserSearch => KeyCode::BrowserSearch,
+        WinitKeyCode::BrowserStop => KeyCode::BrowserStop,
+        WinitKeyCode::Eject => KeyCode::Eject,
+        WinitKeyCode::LaunchApp1 => KeyCode::LaunchApp1,
+        WinitKeyCode::LaunchApp2 => KeyCode::LaunchApp2,
+        WinitKeyCode::LaunchMail => KeyCode::LaunchMail,
+        WinitKeyCode::MediaPlayPause => KeyCode::MediaPlayPause,
+        WinitKeyCode::MediaSelect => KeyCode::MediaSelect,
+        WinitKeyCode::MediaStop => KeyCode::MediaStop,
+        WinitKeyCode::MediaTrackNext => KeyCode::MediaTrackNext,
+        WinitKeyCode::MediaTrackPrevious => KeyCode::MediaTrackPrevious,
+        WinitKeyCode::Power => KeyCode::Power,
+        WinitKeyCode::Sleep => KeyCode::Sleep,
+        WinitKeyCode::AudioVolumeDown => KeyCode::AudioVolumeDown,
+        WinitKeyCode::AudioVolumeMute => KeyCode::AudioVolumeMute,
+        WinitKeyCode::AudioVolumeUp => KeyCode::AudioVolumeUp,
+        WinitKeyCode::WakeUp => KeyCode::WakeUp,
+        WinitKeyCode::Meta => KeyCode::Meta,
+        WinitKeyCode::Hyper => KeyCode::Hyper,
+        WinitKeyCode::Turbo => KeyCode::Turbo,
+        WinitKeyCode::Abort => KeyCode::Abort,
+        WinitKeyCode::Resume => KeyCode::Resume,
+        WinitKeyCode::Suspend => KeyCode::Suspend,
+        WinitKeyCode::Again => KeyCode::Again,
+        WinitKeyCode::Copy => KeyCode::Copy,
+        WinitKeyCode::Cut => KeyCode::Cut,
+        WinitKeyCode::Find => KeyCode::Find,
+        WinitKeyCode::Open => KeyCode::Open,
+        WinitKeyCode::Paste => KeyCode::Paste,
+        WinitKeyCode::Props => KeyCode::Props,
+        WinitKeyCode::Select => KeyCode::Select,
+        WinitKeyCode::Undo => KeyCode::Undo,
+        WinitKeyCode::Hiragana => KeyCode::Hiragana,
+        WinitKeyCode::Katakana => KeyCode::Katakana,
+        WinitKeyCode::F1 => KeyCode::F1,
+        WinitKeyCode::F2 => KeyCode::F2,
+        WinitKeyCode::F3 => KeyCode::F3,
+        WinitKeyCode::F4 => KeyCode::F4,
+        WinitKeyCode::F5 => KeyCode::F5,
+        WinitKeyCode::F6 => KeyCode::F6,
+        WinitKeyCode::F7 => KeyCode::F7,
+        WinitKeyCode::F8 => KeyCode::F8,
+        WinitKeyCode::F9 => KeyCode::F9,
+        WinitKeyCode::F10 => KeyCode::F10,
+        WinitKeyCode::F11 => KeyCode::F11,
+        WinitKeyCode::F12 => KeyCode::F12,
+        WinitKeyCode::F13 => KeyCode::F13,
+        WinitKeyCode::F14 => KeyCode::F14,
+        WinitKeyCode::F15 => KeyCode::F15,
+        WinitKeyCode::F16 => KeyCode::F16,
+        WinitKeyCode::F17 => KeyCode::F17,
+        WinitKeyCode::F18 => KeyCode::F18,
+        WinitKeyCode::F19 => KeyCode::F19,
+        WinitKeyCode::F20 => KeyCode::F20,
+        WinitKeyCode::F21 => KeyCode::F21,
+        WinitKeyCode::F22 => KeyCode::F22,
+        WinitKeyCode::F23 => KeyCode::F23,
+        WinitKeyCode::F24 => KeyCode::F24,
+        WinitKeyCode::F25 => KeyCode::F25,
+        WinitKeyCode::F26 => KeyCode::F26,
+        WinitKeyCode::F27 => KeyCode::F27,
+        WinitKeyCode::F28 => KeyCode::F28,
+        WinitKeyCode::F29 => KeyCode::F29,
+        WinitKeyCode::F30 => KeyCode::F30,
+        WinitKeyCode::F31 => KeyCode::F31,
+        WinitKeyCode::F32 => KeyCode::F32,
+        WinitKeyCode::F33 => KeyCode::F33,
+        WinitKeyCode::F34 => KeyCode::F34,
+        WinitKeyCode::F35 => KeyCode::F35,
+        _ => KeyCode::Unidentified,
+    }
+}
+
+/// Converts winit modifiers into ralaire modifiers.
+pub fn modifiers(modifiers: winit::keyboard::ModifiersState) -> keyboard::Modifiers {
+    let mut result = keyboard::Modifiers::empty();
+
+    result.set(keyboard::Modifiers::SHIFT, modifiers.shift_key());
+    result.set(keyboard::Modifiers::CTRL, modifiers.control_key());
+    result.set(keyboard::Modifiers::ALT, modifiers.alt_key());
+    result.set(keyboard::Modifiers::SUPER, modifiers.super_key());
+
+    result
+}
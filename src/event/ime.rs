@@ -0,0 +1,14 @@
+/// Input method events, sent to the focused widget while it reports an IME cursor area
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Enabled,
+    /// Text being composed, to be shown at the caret until it is committed.
+    /// `cursor` is the byte range of the caret inside the preedit text, `None` hides the caret
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// Composed text that should be inserted at the caret
+    Commit(String),
+    Disabled,
+}
@@ -1,13 +1,127 @@
 use bitflags::bitflags;
 #[derive(Debug, Clone, PartialEq)]
-pub struct Event {
-    key: KeyCode,
-    modifiers: Modifiers,
+pub enum Event {
+    KeyPressed {
+        /// The logical key, takes the keyboard layout and modifiers into account
+        key: Key,
+        /// The key code of the physical key that was pressed, independent of the layout
+        physical_key: KeyCode,
+        /// The text produced by the key press, if any
+        text: Option<String>,
+        /// `true` if the key is being held down and this event was generated by key repeat
+        repeat: bool,
+        modifiers: Modifiers,
+    },
+    KeyReleased {
+        key: Key,
+        physical_key: KeyCode,
+        modifiers: Modifiers,
+    },
+    ModifiersChanged(Modifiers),
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Key {
+    Named(NamedKey),
+    Character(String),
+    Unidentified,
+}
+
+impl Key {
+    pub fn as_named(&self) -> Option<NamedKey> {
+        match self {
+            Key::Named(named) => Some(*named),
+            _ => None,
+        }
+    }
+    pub fn as_character(&self) -> Option<&str> {
+        match self {
+            Key::Character(character) => Some(character),
+            _ => None,
+        }
+    }
+}
+
+/// Keys which don't produce text, only the most common ones are listed here,
+/// every other key is reported as [`Key::Unidentified`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedKey {
+    Alt,
+    AltGraph,
+    CapsLock,
+    Control,
+    Fn,
+    FnLock,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Symbol,
+    SymbolLock,
+    Meta,
+    Hyper,
+    Super,
+    Enter,
+    Tab,
+    Space,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    End,
+    Home,
+    PageDown,
+    PageUp,
+    Backspace,
+    Clear,
+    Copy,
+    Cut,
+    Delete,
+    Insert,
+    Paste,
+    Redo,
+    Undo,
+    ContextMenu,
+    Escape,
+    Find,
+    Help,
+    Pause,
+    Play,
+    Select,
+    ZoomIn,
+    ZoomOut,
+    PrintScreen,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+}
+
 #[allow(dead_code)]
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum KeyCode {
+    Unidentified,
     Backquote,
     Backslash,
     BracketLeft,
@@ -208,7 +322,7 @@ pub enum KeyCode {
 
 bitflags! {
     /// The current state of the keyboard modifiers.
-    #[derive(Default, Debug, Clone, PartialEq)]
+    #[derive(Default, Debug, Clone, Copy, PartialEq)]
     pub struct Modifiers: u32{
         /// The "shift" key.
         const SHIFT = 0b100;
@@ -221,3 +335,33 @@ bitflags! {
         const SUPER = 0b100 << 9;
     }
 }
+
+impl Modifiers {
+    /// Returns true if the [`SHIFT`] key is pressed in the [`Modifiers`].
+    ///
+    /// [`SHIFT`]: Self::SHIFT
+    pub fn shift(self) -> bool {
+        self.contains(Self::SHIFT)
+    }
+
+    /// Returns true if the [`CTRL`] key is pressed in the [`Modifiers`].
+    ///
+    /// [`CTRL`]: Self::CTRL
+    pub fn control(self) -> bool {
+        self.contains(Self::CTRL)
+    }
+
+    /// Returns true if the [`ALT`] key is pressed in the [`Modifiers`].
+    ///
+    /// [`ALT`]: Self::ALT
+    pub fn alt(self) -> bool {
+        self.contains(Self::ALT)
+    }
+
+    /// Returns true if the [`SUPER`] key is pressed in the [`Modifiers`].
+    ///
+    /// [`SUPER`]: Self::SUPER
+    pub fn logo(self) -> bool {
+        self.contains(Self::SUPER)
+    }
+}
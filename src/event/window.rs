@@ -1,3 +1,4 @@
+use super::ime;
 use super::keyboard;
 use super::mouse;
 use super::touch;
@@ -9,7 +10,12 @@ pub enum Event {
     CloseRequested,
     ScaleFactorChanged(f64),
     RedrawRequested,
+    /// The system switched between a light and dark colour scheme
+    ThemeChanged {
+        prefers_dark: bool,
+    },
     Keyboard(keyboard::Event),
+    Ime(ime::Event),
     Mouse(mouse::Event),
     Touch(touch::Event),
 }
@@ -1,14 +1,20 @@
 mod animation;
 pub mod app;
+mod clipboard;
 mod event;
 mod padding;
 mod renderer;
 mod scene;
+mod subscription;
 mod task;
+mod theme;
 mod window;
 pub use animation::Animation;
 pub use animation::AnimationDirection;
 pub use animation::EasingCurve;
+pub use clipboard::{
+    Clipboard, ClipboardBackend, ClipboardContent, ClipboardFormat, ClipboardImage, MemoryClipboard,
+};
 use core::any::Any;
 pub trait AsAny: 'static {
     fn as_any_mut(&mut self) -> &mut dyn Any;
@@ -30,6 +36,8 @@ impl<T: 'static> AsAny for T {
 
 use app::InternalMessage;
 use padding::Padding;
+pub use subscription::Subscription;
 pub use task::Task;
+pub use theme::{Palette, Theme};
 pub mod view;
 pub mod widget;
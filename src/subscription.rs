@@ -0,0 +1,81 @@
+use core::any::TypeId;
+use core::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+use std::time::{Duration, Instant};
+
+use futures::stream::{BoxStream, Stream, StreamExt};
+
+/// Long running sources of state updates, like timers or streams of external events.
+/// The app asks for its subscriptions after every state change, subscriptions that
+/// are new get started and the ones that are gone get cancelled
+pub struct Subscription<State> {
+    pub(crate) recipes: Vec<Recipe<State>>,
+}
+
+pub(crate) struct Recipe<State> {
+    pub id: u64,
+    pub stream: BoxStream<'static, Box<dyn FnOnce(&mut State) + Send>>,
+}
+
+impl<State: 'static> Subscription<State> {
+    pub fn none() -> Self {
+        Self { recipes: vec![] }
+    }
+    pub fn batch(subscriptions: impl IntoIterator<Item = Self>) -> Self {
+        Self {
+            recipes: subscriptions
+                .into_iter()
+                .flat_map(|subscription| subscription.recipes)
+                .collect(),
+        }
+    }
+    /// Calls `callback` every `interval`, starting right away
+    pub fn interval(interval: Duration, callback: fn(&mut State, Instant)) -> Self {
+        struct Interval;
+        // the timer is created on first poll, it needs to be inside of the tokio runtime
+        let stream = futures::stream::unfold(None, move |timer| async move {
+            let mut timer = timer.unwrap_or_else(|| tokio::time::interval(interval));
+            let instant = timer.tick().await.into_std();
+            Some((instant, Some(timer)))
+        });
+        Self::from_stream(
+            recipe_id::<Interval>(&(interval, callback as usize)),
+            stream,
+            callback,
+        )
+    }
+    /// Calls `callback` for every item of `stream`. `id` identifies the subscription
+    /// between updates, the stream is only polled for the first subscription with that id
+    pub fn run<I, S, T>(id: I, stream: S, callback: fn(&mut State, T)) -> Self
+    where
+        I: Hash + 'static,
+        S: Stream<Item = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        Self::from_stream(recipe_id::<I>(&(id, callback as usize)), stream, callback)
+    }
+
+    fn from_stream<S, T>(id: u64, stream: S, callback: fn(&mut State, T)) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let stream = stream.map(move |item| {
+            Box::new(move |state: &mut State| callback(state, item))
+                as Box<dyn FnOnce(&mut State) + Send>
+        });
+        Self {
+            recipes: vec![Recipe {
+                id,
+                stream: stream.boxed(),
+            }],
+        }
+    }
+}
+
+fn recipe_id<I: Hash + 'static>(id: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    TypeId::of::<I>().hash(&mut hasher);
+    id.hash(&mut hasher);
+    hasher.finish()
+}
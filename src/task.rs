@@ -1,4 +1,8 @@
-use crate::Animation;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Instant;
+
+use crate::{Animation, ClipboardContent, ClipboardFormat};
 
 pub struct Task<State> {
     pub(crate) tasks: Vec<InternalTask<State>>,
@@ -14,9 +18,46 @@ impl<State> Task<State> {
     ) -> Self {
         Self {
             tasks: vec![InternalTask::Animate {
-                _animation: animation,
-                _tick_callback: Box::new(tick_callback),
-                _done_callback: Box::new(done_callback),
+                animation,
+                tick_callback: Box::new(tick_callback),
+                done_callback: Box::new(done_callback),
+            }],
+        }
+    }
+    /// Runs `future` on the app's tokio runtime and hands its output to `callback`
+    /// once it completes, the view is rebuilt afterwards
+    pub fn perform<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + 'static,
+        callback: impl FnOnce(&mut State, T) + Send + 'static,
+    ) -> Self
+    where
+        State: 'static,
+    {
+        let future = async move {
+            let output = future.await;
+            Box::new(move |state: &mut State| callback(state, output))
+                as Box<dyn FnOnce(&mut State) + Send>
+        };
+        Self {
+            tasks: vec![InternalTask::Perform(Box::pin(future))],
+        }
+    }
+    /// Puts `content` on the clipboard of the app
+    pub fn clipboard_write(content: impl Into<ClipboardContent>) -> Self {
+        Self {
+            tasks: vec![InternalTask::ClipboardWrite(content.into())],
+        }
+    }
+    /// Reads content of `format` from the clipboard of the app and hands it to `callback`,
+    /// `None` if the clipboard holds nothing of that format. The view is rebuilt afterwards
+    pub fn clipboard_read(
+        format: ClipboardFormat,
+        callback: impl FnOnce(&mut State, Option<ClipboardContent>) + Send + 'static,
+    ) -> Self {
+        Self {
+            tasks: vec![InternalTask::ClipboardRead {
+                format,
+                callback: Box::new(callback),
             }],
         }
     }
@@ -26,10 +67,62 @@ impl<State> Task<State> {
     }
 }
 
+/// Event handlers that don't need to run anything return `()`
+impl<State> From<()> for Task<State> {
+    fn from(_: ()) -> Self {
+        Self::none()
+    }
+}
+
 pub(crate) enum InternalTask<State> {
     Animate {
-        _animation: Animation,
-        _tick_callback: Box<dyn Fn(&mut State)>,
-        _done_callback: Box<dyn Fn(&mut State)>,
+        animation: Animation,
+        tick_callback: Box<dyn Fn(&mut State)>,
+        done_callback: Box<dyn Fn(&mut State)>,
+    },
+    Perform(Pin<Box<dyn Future<Output = Box<dyn FnOnce(&mut State) + Send>> + Send>>),
+    ClipboardWrite(ClipboardContent),
+    ClipboardRead {
+        format: ClipboardFormat,
+        callback: Box<dyn FnOnce(&mut State, Option<ClipboardContent>) + Send>,
     },
 }
+
+/// An animation driven by the app, ticked every `animation.update_interval()`
+pub(crate) struct RunningAnimation<State> {
+    animation: Animation,
+    tick_callback: Box<dyn Fn(&mut State)>,
+    done_callback: Box<dyn Fn(&mut State)>,
+    next_tick: Instant,
+}
+
+impl<State> RunningAnimation<State> {
+    pub fn new(
+        animation: Animation,
+        tick_callback: Box<dyn Fn(&mut State)>,
+        done_callback: Box<dyn Fn(&mut State)>,
+    ) -> Self {
+        Self {
+            animation,
+            tick_callback,
+            done_callback,
+            next_tick: Instant::now(),
+        }
+    }
+    pub fn next_tick(&self) -> Instant {
+        self.next_tick
+    }
+    /// Advances the animation by one step, returns false once it finished
+    pub fn tick(&mut self, state: &mut State) -> bool {
+        if !self.animation.is_finished() {
+            self.animation.tick();
+            (self.tick_callback)(state);
+        }
+        if self.animation.is_finished() {
+            (self.done_callback)(state);
+            return false;
+        }
+        self.next_tick += self.animation.update_interval();
+        true
+    }
+}
@@ -0,0 +1,101 @@
+use ashpd::desktop::settings::{ColorScheme, Settings};
+use futures::StreamExt;
+use vello::peniko::Color;
+
+/// The look of the app, asked for with [`App::theme`](crate::app::App::theme)
+/// after every state change
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+    /// Light or dark, following the colour scheme preference of the desktop. It is
+    /// read from the settings portal and followed when the user switches it
+    System,
+    Custom(Box<Palette>),
+}
+
+impl Theme {
+    /// `prefers_dark` is the preference of the system, only used by [`Theme::System`]
+    pub fn palette(&self, prefers_dark: bool) -> Palette {
+        match self {
+            Theme::Light => Palette::light(),
+            Theme::Dark => Palette::dark(),
+            Theme::System if prefers_dark => Palette::dark(),
+            Theme::System => Palette::light(),
+            Theme::Custom(palette) => (**palette).clone(),
+        }
+    }
+}
+
+/// Named colours, radii, spacing and fonts every widget is laid out and painted with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    /// Window background
+    pub background: Color,
+    /// Text and icons
+    pub foreground: Color,
+    /// Focus rings and text selections
+    pub accent: Color,
+    /// Background of buttons and slider tracks
+    pub control: Color,
+    /// Slider handles
+    pub handle: Color,
+    pub window_button: Color,
+    pub window_button_hover: Color,
+    pub shadow: Color,
+    /// Corner radius of buttons that don't set their own
+    pub radius: f64,
+    /// Gap between flex children and default padding of containers and the header
+    pub spacing: f64,
+    /// Tried in order until one of them is installed
+    pub font_families: Vec<String>,
+    pub font_size: f32,
+}
+
+impl Palette {
+    pub fn light() -> Self {
+        Self {
+            background: Color::WHITE,
+            foreground: Color::BLACK,
+            accent: Color::rgb8(53, 132, 228),
+            control: Color::LIGHT_GRAY,
+            handle: Color::GRAY,
+            window_button: Color::rgb8(235, 235, 235),
+            window_button_hover: Color::rgb8(223, 223, 223),
+            shadow: Color::rgba8(0, 0, 0, 100),
+            radius: 10.,
+            spacing: 6.,
+            font_families: vec!["Inter".to_owned(), "Noto Sans".to_owned()],
+            font_size: 14.6666666,
+        }
+    }
+    pub fn dark() -> Self {
+        Self {
+            background: Color::rgb8(36, 36, 36),
+            foreground: Color::WHITE,
+            accent: Color::rgb8(120, 174, 237),
+            control: Color::rgb8(70, 70, 70),
+            handle: Color::rgb8(150, 150, 150),
+            window_button: Color::rgb8(55, 55, 55),
+            window_button_hover: Color::rgb8(68, 68, 68),
+            shadow: Color::rgba8(0, 0, 0, 160),
+            ..Self::light()
+        }
+    }
+}
+
+/// Reads whether the desktop prefers a dark colour scheme from the XDG settings portal,
+/// `on_change` gets it once right away and again every time the user switches it
+pub(crate) async fn watch_color_scheme(on_change: impl Fn(bool)) -> ashpd::Result<()> {
+    let settings = Settings::new().await?;
+    on_change(matches!(
+        settings.color_scheme().await?,
+        ColorScheme::PreferDark
+    ));
+    let mut changes = settings.receive_color_scheme_changed().await?;
+    while let Some(color_scheme) = changes.next().await {
+        on_change(matches!(color_scheme, ColorScheme::PreferDark));
+    }
+    Ok(())
+}
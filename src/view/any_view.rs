@@ -0,0 +1,75 @@
+use crate::view::{View, ViewMarker};
+use crate::widget::Widget;
+use crate::AsAny;
+
+/// A view with its type erased, boxed as `Box<dyn AnyWidgetView<State>>` it is a
+/// [`View`] itself. Lets views of different types go where a single type is expected,
+/// for example windows that show different content:
+/// ```ignore
+/// fn app_logic(state: &mut State) -> Vec<(Key, Box<dyn AnyWidgetView<State>>)> {
+///     vec![(Key::Main, Box::new(main_window(state))), (Key::Settings, Box::new(settings_window(state)))]
+/// }
+/// ```
+/// When a rebuild finds a view of another type than before, the old widget is torn
+/// down and a new one is built in its place
+pub trait AnyWidgetView<State: 'static>: AsAny {
+    fn dyn_build(&self) -> Box<dyn Widget<State>>;
+    fn dyn_rebuild(&self, old: &dyn AnyWidgetView<State>, element: &mut Box<dyn Widget<State>>);
+    fn dyn_teardown(&self, element: &mut Box<dyn Widget<State>>);
+}
+
+impl<State: 'static, V> AnyWidgetView<State> for V
+where
+    V: View<State> + ViewMarker,
+    V::Element: Widget<State>,
+{
+    fn dyn_build(&self) -> Box<dyn Widget<State>> {
+        Box::new(self.build())
+    }
+
+    fn dyn_rebuild(&self, old: &dyn AnyWidgetView<State>, element: &mut Box<dyn Widget<State>>) {
+        match old.as_any().downcast_ref::<V>() {
+            Some(old) => self.rebuild(
+                old,
+                (**element)
+                    .as_any_mut()
+                    .downcast_mut::<V::Element>()
+                    .unwrap(),
+            ),
+            None => {
+                old.dyn_teardown(element);
+                *element = self.dyn_build();
+            }
+        }
+    }
+
+    fn dyn_teardown(&self, element: &mut Box<dyn Widget<State>>) {
+        self.teardown(
+            (**element)
+                .as_any_mut()
+                .downcast_mut::<V::Element>()
+                .unwrap(),
+        )
+    }
+}
+
+impl<State: 'static> ViewMarker for Box<dyn AnyWidgetView<State>> {}
+impl<State: 'static> View<State> for Box<dyn AnyWidgetView<State>> {
+    type Element = Box<dyn Widget<State>>;
+
+    fn build(&self) -> Self::Element {
+        (**self).dyn_build()
+    }
+
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        (**self).dyn_rebuild(&**old, element)
+    }
+
+    fn teardown(&self, element: &mut Self::Element) {
+        (**self).dyn_teardown(element)
+    }
+
+    fn get_name(&self) -> &'static str {
+        (**self).type_name()
+    }
+}
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use crate::view::{View, ViewMarker};
 use crate::widget::{self, Widget};
+use crate::Task;
 use vello::peniko::kurbo::{RoundedRectRadii, Size};
 use vello::peniko::Color;
 
@@ -11,9 +13,9 @@ pub fn button<State, Child>(child: Child) -> Button<State, Child> {
 
 pub struct Button<State, Child> {
     size: Size,
-    radii: RoundedRectRadii,
-    color: Color,
-    on_press: Option<fn(&mut State)>,
+    radii: Option<RoundedRectRadii>,
+    color: Option<Color>,
+    on_press: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
     child: Child,
     phantom_data: PhantomData<State>,
 }
@@ -22,23 +24,29 @@ impl<State, Child> Button<State, Child> {
     pub fn new(child: Child) -> Self {
         Self {
             size: Size::new(60. * 1.5, 23. * 1.5),
-            radii: 10.0.into(),
-            color: Color::LIGHT_GRAY,
+            radii: None,
+            color: None,
             on_press: None,
             child,
             phantom_data: PhantomData,
         }
     }
+    /// Overrides the radius of the theme
     pub fn radius(mut self, radius: f64) -> Self {
-        self.radii = radius.into();
+        self.radii = Some(radius.into());
         self
     }
+    /// Overrides the control colour of the theme
     pub fn color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.color = Some(color);
         self
     }
-    pub fn on_press(mut self, on_press: fn(&mut State)) -> Self {
-        self.on_press = Some(on_press);
+    /// `on_press` can return a [`Task`] to run, or `()`
+    pub fn on_press<T: Into<Task<State>>>(
+        mut self,
+        on_press: impl Fn(&mut State) -> T + Send + Sync + 'static,
+    ) -> Self {
+        self.on_press = Some(Arc::new(move |state| on_press(state).into()));
         self
     }
 }
@@ -57,8 +65,7 @@ where
             self.size,
             self.radii,
             self.color,
-            self.on_press
-                .map(|f| Box::new(f.clone()) as Box<dyn Fn(&mut State) + Send + Sync + 'static>),
+            self.on_press.clone(),
         )
     }
 
@@ -69,10 +76,9 @@ where
             }
             element.color = self.color;
             element.radii = self.radii;
-            element.on_press = self
-                .on_press
-                .map(|f| Box::new(f.clone()) as Box<dyn Fn(&mut State) + Send + Sync + 'static>);
         }
+        // closures can't be compared, so always hand over the new one
+        element.on_press = self.on_press.clone();
         // there is only one child...
         for child in element.children_mut() {
             self.child.rebuild(
@@ -9,7 +9,7 @@ pub fn container<Child>(child: Child) -> Container<Child> {
 pub struct Container<Child> {
     h_alignment: alignment::Horizontal,
     v_alignment: alignment::Vertical,
-    padding: Padding,
+    padding: Option<Padding>,
     child: Child,
 }
 
@@ -18,12 +18,14 @@ impl<Child> Container<Child> {
         Self {
             h_alignment: alignment::Horizontal::Center,
             v_alignment: alignment::Vertical::Center,
-            padding: Padding::ZERO,
+            padding: None,
             child,
         }
     }
+    /// Overrides the padding, which follows [`Palette::spacing`](crate::Palette::spacing)
+    /// by default
     pub fn pad<P: Into<Padding>>(mut self, padding: P) -> Self {
-        self.padding = padding.into();
+        self.padding = Some(padding.into());
         self
     }
     pub fn h_align(mut self, h_alignment: alignment::Horizontal) -> Self {
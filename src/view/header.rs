@@ -1,11 +1,12 @@
 use crate::view::{View, ViewMarker, WindowControls};
-use crate::widget::{self, Widget};
+use crate::widget::{self, ButtonLayout, Widget};
 
 pub struct Header<Left, Middle, Right> {
     left: Left,
     middle: Middle,
     right: Right,
-    window_controls: WindowControls,
+    start_controls: WindowControls,
+    end_controls: WindowControls,
 }
 impl<Left, Middle, Right> Header<Left, Middle, Right> {
     pub fn new(left: Left, middle: Middle, right: Right) -> Self {
@@ -13,8 +14,27 @@ impl<Left, Middle, Right> Header<Left, Middle, Right> {
             left,
             middle,
             right,
-            window_controls: WindowControls,
+            start_controls: WindowControls::new(vec![]),
+            end_controls: WindowControls::new(vec![]),
         }
+        .button_layout(ButtonLayout::default())
+    }
+    /// Swaps the contents of the header, keeping the window buttons
+    pub fn with_children<L, M, R>(self, left: L, middle: M, right: R) -> Header<L, M, R> {
+        Header {
+            left,
+            middle,
+            right,
+            start_controls: self.start_controls,
+            end_controls: self.end_controls,
+        }
+    }
+    /// Which window buttons are shown on which side, see [`ButtonLayout`]
+    pub fn button_layout(mut self, button_layout: impl Into<ButtonLayout>) -> Self {
+        let button_layout = button_layout.into();
+        self.start_controls = WindowControls::new(button_layout.start);
+        self.end_controls = WindowControls::new(button_layout.end);
+        self
     }
 }
 impl<Left, Middle, Right> ViewMarker for Header<Left, Middle, Right> {}
@@ -33,9 +53,10 @@ where
         let left = self.left.build();
         let middle = self.middle.build();
         let right = self.right.build();
-        let window_controls = self.window_controls.build();
+        let start_controls = self.start_controls.build();
+        let end_controls = self.end_controls.build();
 
-        widget::Header::new(left, middle, right, window_controls)
+        widget::Header::new(left, middle, right, start_controls, end_controls)
     }
 
     fn rebuild(&self, old: &Self, element: &mut Self::Element) {
@@ -60,9 +81,16 @@ where
                 .downcast_mut::<Right::Element>()
                 .unwrap(),
         );
-        self.window_controls.rebuild(
-            &old.window_controls,
-            (*element.window_controls.inner)
+        self.start_controls.rebuild(
+            &old.start_controls,
+            (*element.start_controls.inner)
+                .as_any_mut()
+                .downcast_mut::<widget::WindowControls<State>>()
+                .unwrap(),
+        );
+        self.end_controls.rebuild(
+            &old.end_controls,
+            (*element.end_controls.inner)
                 .as_any_mut()
                 .downcast_mut::<widget::WindowControls<State>>()
                 .unwrap(),
@@ -88,8 +116,14 @@ where
                 .downcast_mut::<Right::Element>()
                 .unwrap(),
         );
-        self.window_controls.teardown(
-            (*element.window_controls.inner)
+        self.start_controls.teardown(
+            (*element.start_controls.inner)
+                .as_any_mut()
+                .downcast_mut::<widget::WindowControls<State>>()
+                .unwrap(),
+        );
+        self.end_controls.teardown(
+            (*element.end_controls.inner)
                 .as_any_mut()
                 .downcast_mut::<widget::WindowControls<State>>()
                 .unwrap(),
@@ -1,3 +1,5 @@
+mod any_view;
+pub use any_view::*;
 mod bar;
 // pub use bar::*;
 mod button;
@@ -10,13 +12,22 @@ mod header;
 pub use header::*;
 mod image;
 pub use image::*;
+mod rich_text;
+pub use rich_text::*;
 mod root_view;
 pub use root_view::*;
 mod scroll;
 // pub use scroll::*;
 mod slider;
 pub use slider::*;
+mod styled;
+pub use styled::*;
 mod text;
+pub use text::*;
+mod text_editor;
+pub use text_editor::*;
+mod text_input;
+pub use text_input::*;
 mod view;
 pub use view::*;
 mod window;
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use crate::view::{View, ViewMarker};
+use crate::widget::{self, FontWeight, SpanStyle, TextAlign, TextSpan, TextStyle};
+use crate::Task;
+use vello::peniko::Color;
+
+pub fn rich_text<State>(spans: impl IntoIterator<Item = Span<State>>) -> RichText<State> {
+    RichText::new(spans.into_iter().collect())
+}
+
+pub fn span<State>(text: impl Into<String>) -> Span<State> {
+    Span::new(text.into())
+}
+
+pub struct Span<State> {
+    text: String,
+    style: SpanStyle,
+    on_click: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
+}
+
+impl<State> Span<State> {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            style: SpanStyle::default(),
+            on_click: None,
+        }
+    }
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.style.weight = Some(weight);
+        self
+    }
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+    pub fn size(mut self, size: f32) -> Self {
+        self.style.size = Some(size);
+        self
+    }
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = italic;
+        self
+    }
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.style.underline = underline;
+        self
+    }
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.style.strikethrough = strikethrough;
+        self
+    }
+    /// Turns the span into a link, `on_click` can return a [`Task`] to run, or `()`
+    pub fn on_click<T: Into<Task<State>>>(
+        mut self,
+        on_click: impl Fn(&mut State) -> T + Send + Sync + 'static,
+    ) -> Self {
+        self.on_click = Some(Arc::new(move |state| on_click(state).into()));
+        self
+    }
+    fn build(&self) -> TextSpan<State> {
+        TextSpan::new(self.text.clone(), self.style.clone(), self.on_click.clone())
+    }
+}
+
+pub struct RichText<State> {
+    spans: Vec<Span<State>>,
+    style: TextStyle,
+}
+
+impl<State> RichText<State> {
+    pub fn new(spans: Vec<Span<State>>) -> Self {
+        Self {
+            spans,
+            style: TextStyle::default(),
+        }
+    }
+    /// Size of spans that don't set their own
+    pub fn size(mut self, size: f32) -> Self {
+        self.style.font_size = Some(size);
+        self
+    }
+    /// Colour of spans that don't set their own
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+    /// Multiple of the font size
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.style.line_height = line_height;
+        self
+    }
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.style.align = align;
+        self
+    }
+}
+
+impl<State> ViewMarker for RichText<State> {}
+impl<State: 'static> View<State> for RichText<State> {
+    type Element = widget::RichText<State>;
+    fn build(&self) -> Self::Element {
+        widget::RichText::new(
+            self.spans.iter().map(Span::build).collect(),
+            self.style.clone(),
+        )
+    }
+
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        if self.style != old.style {
+            element.style = self.style.clone();
+            element.invalidate_layout();
+        }
+        element.truncate_spans(self.spans.len());
+        for (idx, span) in self.spans.iter().enumerate() {
+            match old.spans.get(idx) {
+                Some(old) if old.text == span.text && old.style == span.style => {
+                    // closures can't be compared, so always hand over the new one
+                    element.set_span_on_click(idx, span.on_click.clone());
+                }
+                Some(_) => element.update_span(idx, span.build()),
+                None => element.push_span(span.build()),
+            }
+        }
+    }
+
+    fn teardown(&self, _: &mut Self::Element) {}
+}
@@ -1,33 +1,46 @@
+use std::sync::Arc;
+
 use crate::view::{View, ViewMarker};
 use crate::widget;
+use crate::Task;
 
-pub fn slider<State>(value: f64, on_change: fn(&mut State, f64)) -> Slider<State> {
+/// `on_change` can return a [`Task`] to run, or `()`
+pub fn slider<State, T: Into<Task<State>>>(
+    value: f64,
+    on_change: impl Fn(&mut State, f64) -> T + Send + Sync + 'static,
+) -> Slider<State> {
     Slider::new(value, on_change)
 }
 
 pub struct Slider<State> {
     value: f64,
-    on_change: fn(&mut State, f64),
+    on_change: Arc<dyn Fn(&mut State, f64) -> Task<State> + Send + Sync + 'static>,
 }
 
 impl<State> Slider<State> {
-    pub fn new(value: f64, on_change: fn(&mut State, f64)) -> Self
-where {
-        Self { value, on_change }
+    pub fn new<T: Into<Task<State>>>(
+        value: f64,
+        on_change: impl Fn(&mut State, f64) -> T + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            value,
+            on_change: Arc::new(move |state, value| on_change(state, value).into()),
+        }
     }
 }
 impl<State> ViewMarker for Slider<State> {}
 impl<State: 'static> View<State> for Slider<State> {
     type Element = widget::Slider<State>;
     fn build(&self) -> Self::Element {
-        widget::Slider::new(self.value, Box::new(self.on_change.clone()))
+        widget::Slider::new(self.value, self.on_change.clone())
     }
 
     fn rebuild(&self, old: &Self, element: &mut Self::Element) {
-        if self.value != old.value || !core::ptr::fn_addr_eq(self.on_change, old.on_change) {
-            element.on_change = Box::new(self.on_change.clone());
+        if self.value != old.value {
             element.value = self.value;
         }
+        // closures can't be compared, so always hand over the new one
+        element.on_change = self.on_change.clone();
     }
 
     fn teardown(&self, _: &mut Self::Element) {}
@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use crate::view::{View, ViewMarker};
+use crate::widget::{self, Style, Widget};
+use vello::kurbo::RoundedRectRadii;
+use vello::peniko::Color;
+
+/// Decorates any view with a background, border, opacity or foreground colour.
+/// Calls chain on the returned [`Styled`] instead of wrapping it again
+pub trait StyleExt<State: 'static>: View<State> {
+    fn background(self, color: Color) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).background(color)
+    }
+    fn border(self, width: f64, color: Color) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).border(width, color)
+    }
+    fn border_width(self, width: f64) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).border_width(width)
+    }
+    fn border_color(self, color: Color) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).border_color(color)
+    }
+    fn border_radius(self, radius: f64) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).border_radius(radius)
+    }
+    fn corner_radii(self, radii: impl Into<RoundedRectRadii>) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).corner_radii(radii)
+    }
+    fn opacity(self, opacity: f32) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).opacity(opacity)
+    }
+    fn foreground(self, color: Color) -> Styled<State, Self>
+    where
+        Self: Sized,
+    {
+        Styled::new(self).foreground(color)
+    }
+}
+
+impl<State: 'static, V: View<State>> StyleExt<State> for V {}
+
+pub struct Styled<State, V> {
+    view: V,
+    style: Style,
+    phantom_data: PhantomData<State>,
+}
+
+impl<State, V> Styled<State, V> {
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            style: Style::default(),
+            phantom_data: PhantomData,
+        }
+    }
+    pub fn background(mut self, color: Color) -> Self {
+        self.style.background = Some(color);
+        self
+    }
+    pub fn border(mut self, width: f64, color: Color) -> Self {
+        self.style.border_width = width;
+        self.style.border_color = Some(color);
+        self
+    }
+    pub fn border_width(mut self, width: f64) -> Self {
+        self.style.border_width = width;
+        self
+    }
+    pub fn border_color(mut self, color: Color) -> Self {
+        self.style.border_color = Some(color);
+        self
+    }
+    /// Same radius for every corner
+    pub fn border_radius(mut self, radius: f64) -> Self {
+        self.style.radii = RoundedRectRadii::from_single_radius(radius);
+        self
+    }
+    pub fn corner_radii(mut self, radii: impl Into<RoundedRectRadii>) -> Self {
+        self.style.radii = radii.into();
+        self
+    }
+    /// Between 0 (invisible) and 1 (opaque), applied to the whole subtree
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.style.opacity = opacity.clamp(0., 1.);
+        self
+    }
+    /// Colour of text and icons inside
+    pub fn foreground(mut self, color: Color) -> Self {
+        self.style.foreground = Some(color);
+        self
+    }
+}
+
+impl<State, V> ViewMarker for Styled<State, V> {}
+impl<State, V> View<State> for Styled<State, V>
+where
+    State: 'static,
+    V: View<State>,
+    V::Element: Widget<State>,
+{
+    type Element = widget::Styled<State>;
+
+    fn build(&self) -> Self::Element {
+        widget::Styled::new(self.view.build(), self.style.clone())
+    }
+
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        if self.style != old.style {
+            element.style = self.style.clone();
+        }
+        // there is only one child...
+        for child in element.children_mut() {
+            self.view.rebuild(
+                &old.view,
+                (*child.inner)
+                    .as_any_mut()
+                    .downcast_mut::<V::Element>()
+                    .unwrap(),
+            )
+        }
+    }
+
+    fn teardown(&self, element: &mut Self::Element) {
+        for child in element.children_mut() {
+            self.view.teardown(
+                (*child.inner)
+                    .as_any_mut()
+                    .downcast_mut::<V::Element>()
+                    .unwrap(),
+            )
+        }
+    }
+}
@@ -1,10 +1,112 @@
 use crate::view::{View, ViewMarker};
-use crate::widget;
+use crate::widget::{self, FontWeight, TextAlign, TextOverflow, TextStyle};
+use vello::peniko::Color;
+
+pub fn text(text: impl Into<String>) -> Text {
+    Text::new(text.into())
+}
+
+/// Text with its own font, colour and alignment, a plain `String` uses the palette for all of them
+pub struct Text {
+    text: String,
+    style: TextStyle,
+    selectable: bool,
+}
+
+impl Text {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            style: TextStyle::default(),
+            selectable: false,
+        }
+    }
+    pub fn font_family(mut self, family: impl Into<String>) -> Self {
+        self.style.font_families = Some(vec![family.into()]);
+        self
+    }
+    /// Tried in order until one of them is installed
+    pub fn font_families(mut self, families: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.style.font_families = Some(families.into_iter().map(Into::into).collect());
+        self
+    }
+    pub fn size(mut self, size: f32) -> Self {
+        self.style.font_size = Some(size);
+        self
+    }
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.style.weight = weight;
+        self
+    }
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = italic;
+        self
+    }
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.style.letter_spacing = letter_spacing;
+        self
+    }
+    /// Multiple of the font size
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.style.line_height = line_height;
+        self
+    }
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.style.align = align;
+        self
+    }
+    /// Lines past `max_lines` are handled by the [`overflow`](Text::overflow) mode
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.style.max_lines = Some(max_lines);
+        self
+    }
+    pub fn overflow(mut self, overflow: TextOverflow) -> Self {
+        self.style.overflow = overflow;
+        self
+    }
+    /// Lets the user select the text with the mouse and copy it with Ctrl+C
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+}
+
+impl ViewMarker for Text {}
+impl<State: 'static> View<State> for Text {
+    type Element = widget::Text;
+    fn build(&self) -> Self::Element {
+        let mut element = widget::Text::new(self.text.clone(), self.style.clone());
+        element.set_selectable(self.selectable);
+        element
+    }
+
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        if self.text != old.text {
+            element.set_text(self.text.clone());
+        }
+        if self.style != old.style {
+            element.style = self.style.clone();
+        }
+        if self.selectable != old.selectable {
+            element.set_selectable(self.selectable);
+        }
+    }
+
+    fn teardown(&self, _: &mut Self::Element) {}
+}
+
 impl ViewMarker for String {}
 impl<State: 'static> View<State> for String {
     type Element = widget::Text;
     fn build(&self) -> Self::Element {
-        widget::Text::new(self.clone())
+        widget::Text::new(self.clone(), TextStyle::default())
     }
 
     fn rebuild(&self, old: &Self, element: &mut Self::Element) {
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::view::{View, ViewMarker};
+use crate::widget;
+use crate::Task;
+
+/// `on_change` can return a [`Task`] to run, or `()`
+pub fn text_editor<State, T: Into<Task<State>>>(
+    value: String,
+    on_change: impl Fn(&mut State, String) -> T + Send + Sync + 'static,
+) -> TextEditor<State> {
+    TextEditor::new(value, on_change)
+}
+
+pub struct TextEditor<State> {
+    value: String,
+    width: Option<f64>,
+    height: Option<f64>,
+    on_change: Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>,
+}
+
+impl<State> TextEditor<State> {
+    pub fn new<T: Into<Task<State>>>(
+        value: String,
+        on_change: impl Fn(&mut State, String) -> T + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            value,
+            width: None,
+            height: None,
+            on_change: Arc::new(move |state, text| on_change(state, text).into()),
+        }
+    }
+    /// Fixed width, the editor fills the available width otherwise
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+    /// Fixed height, the editor fills the available height otherwise
+    pub fn height(mut self, height: f64) -> Self {
+        self.height = Some(height);
+        self
+    }
+}
+
+impl<State> ViewMarker for TextEditor<State> {}
+impl<State: 'static> View<State> for TextEditor<State> {
+    type Element = widget::TextEditor<State>;
+    fn build(&self) -> Self::Element {
+        widget::TextEditor::new(
+            self.value.clone(),
+            self.width,
+            self.height,
+            self.on_change.clone(),
+        )
+    }
+
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        // Same as text_input, compare against the widget's own copy of the text
+        if self.value != element.text() {
+            element.set_text(self.value.clone());
+        }
+        if self.width != old.width {
+            element.width = self.width;
+        }
+        if self.height != old.height {
+            element.height = self.height;
+        }
+        // closures can't be compared, so always hand over the new one
+        element.on_change = self.on_change.clone();
+    }
+
+    fn teardown(&self, _: &mut Self::Element) {}
+}
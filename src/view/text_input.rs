@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::view::{View, ViewMarker};
+use crate::widget;
+use crate::Task;
+
+/// `on_change` can return a [`Task`] to run, or `()`
+pub fn text_input<State, T: Into<Task<State>>>(
+    value: String,
+    on_change: impl Fn(&mut State, String) -> T + Send + Sync + 'static,
+) -> TextInput<State> {
+    TextInput::new(value, on_change)
+}
+
+pub struct TextInput<State> {
+    value: String,
+    placeholder: String,
+    width: f64,
+    on_change: Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>,
+    on_submit: Option<Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>>,
+}
+
+impl<State> TextInput<State> {
+    pub fn new<T: Into<Task<State>>>(
+        value: String,
+        on_change: impl Fn(&mut State, String) -> T + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            value,
+            placeholder: String::new(),
+            width: 200.,
+            on_change: Arc::new(move |state, text| on_change(state, text).into()),
+            on_submit: None,
+        }
+    }
+    pub fn placeholder(mut self, placeholder: String) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+    pub fn on_submit<T: Into<Task<State>>>(
+        mut self,
+        on_submit: impl Fn(&mut State, String) -> T + Send + Sync + 'static,
+    ) -> Self {
+        self.on_submit = Some(Arc::new(move |state, text| on_submit(state, text).into()));
+        self
+    }
+}
+
+impl<State> ViewMarker for TextInput<State> {}
+impl<State: 'static> View<State> for TextInput<State> {
+    type Element = widget::TextInput<State>;
+    fn build(&self) -> Self::Element {
+        widget::TextInput::new(
+            self.value.clone(),
+            self.placeholder.clone(),
+            self.width,
+            self.on_change.clone(),
+            self.on_submit.clone(),
+        )
+    }
+
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        // The widget edits its own copy of the text, so compare against it
+        // instead of the old view to pick up edits the app logic rejected
+        if self.value != element.text() {
+            element.set_text(self.value.clone());
+        }
+        if self.placeholder != old.placeholder {
+            element.set_placeholder(self.placeholder.clone());
+        }
+        if self.width != old.width {
+            element.width = self.width;
+        }
+        // closures can't be compared, so always hand over the new ones
+        element.on_change = self.on_change.clone();
+        element.on_submit = self.on_submit.clone();
+    }
+
+    fn teardown(&self, _: &mut Self::Element) {}
+}
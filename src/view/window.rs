@@ -1,37 +1,154 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
 use crate::{
-    view::{container, Container, Header, View},
-    widget::{self, Widget},
+    view::{container, text, Container, Header, Text, View, ViewMarker},
+    widget::{self, ButtonLayout, Decorations, TextOverflow, Widget, WindowIcon, WindowSettings},
+    Task,
 };
+use vello::peniko::kurbo::{Point, Size};
 
 // TODO: Come up with better names
-pub fn window<Content>(
+pub fn window<State, Content>(
     content: Content,
     title: String,
-) -> Window<String, Container<String>, String, Content> {
-    let header = Header::new(
-        "left".to_owned(),
-        container(title.clone()),
-        "right".to_owned(),
-    );
+) -> Window<State, String, Container<Text>, String, Content> {
+    // long titles are cut short instead of running under the window controls
+    let title_view = text(title.clone())
+        .max_lines(1)
+        .overflow(TextOverflow::Ellipsis);
+    let header = Header::new(String::new(), container(title_view), String::new());
     Window::new(header, content, title)
 }
-pub struct Window<Left, Middle, Right, Content> {
+pub struct Window<State, Left, Middle, Right, Content> {
     header: Header<Left, Middle, Right>,
     content: Content,
     title: String,
+    show_header: bool,
+    settings: WindowSettings,
+    on_close: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
+    phantom_data: PhantomData<State>,
 }
 
-impl<Left, Middle, Right, Content> Window<Left, Middle, Right, Content> {
+impl<State, Left, Middle, Right, Content> Window<State, Left, Middle, Right, Content> {
     pub fn new(header: Header<Left, Middle, Right>, content: Content, title: String) -> Self {
         Self {
             header,
             content,
             title,
+            show_header: true,
+            settings: WindowSettings::default(),
+            on_close: None,
+            phantom_data: PhantomData,
+        }
+    }
+    /// Called instead of closing the window when the user asks to close it.
+    /// The window stays open until the app logic stops returning it
+    pub fn on_close<T: Into<Task<State>>>(
+        mut self,
+        on_close: impl Fn(&mut State) -> T + Send + Sync + 'static,
+    ) -> Self {
+        self.on_close = Some(Arc::new(move |state| on_close(state).into()));
+        self
+    }
+    /// Replaces the contents of the header bar, `title` is centered between `start` and `end`.
+    /// Presses on header space that none of them capture still drag the window
+    pub fn header<L, M, R>(self, start: L, title: M, end: R) -> Window<State, L, M, R, Content> {
+        Window {
+            header: self.header.with_children(start, title, end),
+            content: self.content,
+            title: self.title,
+            show_header: self.show_header,
+            settings: self.settings,
+            on_close: self.on_close,
+            phantom_data: PhantomData,
         }
     }
+    /// Leaves out the header bar, the content fills the whole window
+    pub fn hide_header(mut self) -> Self {
+        self.show_header = false;
+        self
+    }
+    /// Which window buttons the header shows on which side, for example
+    /// `"close,minimize:"`. See [`ButtonLayout`]
+    pub fn button_layout(mut self, button_layout: impl Into<ButtonLayout>) -> Self {
+        self.header = self.header.button_layout(button_layout);
+        self
+    }
+    /// Size of the window, including its shadow. Changing it resizes the open window
+    pub fn inner_size(mut self, width: f64, height: f64) -> Self {
+        self.settings.inner_size = Some(Size::new(width, height));
+        self
+    }
+    /// Defaults to 200x200
+    pub fn min_inner_size(mut self, width: f64, height: f64) -> Self {
+        self.settings.min_inner_size = Some(Size::new(width, height));
+        self
+    }
+    pub fn max_inner_size(mut self, width: f64, height: f64) -> Self {
+        self.settings.max_inner_size = Some(Size::new(width, height));
+        self
+    }
+    /// Position of the window on the screen, ignored on Wayland
+    pub fn position(mut self, x: f64, y: f64) -> Self {
+        self.settings.position = Some(Point::new(x, y));
+        self
+    }
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.settings.resizable = resizable;
+        self
+    }
+    /// How far the corners of the resize border reach along the window edges,
+    /// the border itself is as wide as the window shadow
+    pub fn resize_grab_width(mut self, resize_grab_width: f64) -> Self {
+        self.settings.resize_grab_width = resize_grab_width;
+        self
+    }
+    pub fn always_on_top(mut self, always_on_top: bool) -> Self {
+        self.settings.always_on_top = always_on_top;
+        self
+    }
+    /// Borderless fullscreen on the current monitor
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.settings.fullscreen = fullscreen;
+        self
+    }
+    pub fn maximized(mut self, maximized: bool) -> Self {
+        self.settings.maximized = maximized;
+        self
+    }
+    /// Client side by default, those fall back to server side decorations
+    /// if the compositor doesn't support them
+    pub fn decorations(mut self, decorations: Decorations) -> Self {
+        self.settings.decorations = decorations;
+        self
+    }
+    /// On by default, the shadow around the window needs it
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.settings.transparent = transparent;
+        self
+    }
+    /// App id on Wayland, only used when the window opens
+    pub fn app_id(mut self, app_id: impl Into<String>) -> Self {
+        self.settings.app_id = Some(app_id.into());
+        self
+    }
+    /// `rgba` holds `width * height` pixels with 4 bytes each
+    pub fn icon(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        self.settings.icon = Some(WindowIcon {
+            rgba,
+            width,
+            height,
+        });
+        self
+    }
+}
+impl<State, Left, Middle, Right, Content> ViewMarker
+    for Window<State, Left, Middle, Right, Content>
+{
 }
 impl<State: 'static, Left, Middle, Right, Content> View<State>
-    for Window<Left, Middle, Right, Content>
+    for Window<State, Left, Middle, Right, Content>
 where
     Left: View<State>,
     Middle: View<State>,
@@ -46,12 +163,27 @@ where
     fn build(&self) -> Self::Element {
         let header = self.header.build();
         let content = self.content.build();
-        widget::Window::new(header, content, self.title.clone())
+        widget::Window::new(
+            header,
+            content,
+            self.title.clone(),
+            self.show_header,
+            self.settings.clone(),
+            self.on_close.clone(),
+        )
     }
     fn rebuild(&self, old: &Self, element: &mut Self::Element) {
         if self.title != old.title {
             element.title.clone_from(&self.title);
         }
+        if self.show_header != old.show_header {
+            element.show_header = self.show_header;
+        }
+        if self.settings != old.settings {
+            element.settings.clone_from(&self.settings);
+        }
+        // closures can't be compared, so always hand over the new one
+        element.on_close = self.on_close.clone();
         self.header.rebuild(
             &old.header,
             (*element.header.inner)
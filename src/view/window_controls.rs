@@ -1,17 +1,28 @@
 use crate::view::{View, ViewMarker};
-use crate::widget;
+use crate::widget::{self, WindowButton};
 
-// TODO: remove this
 impl ViewMarker for WindowControls {}
-pub struct WindowControls;
+pub struct WindowControls {
+    buttons: Vec<WindowButton>,
+}
+
+impl WindowControls {
+    pub fn new(buttons: Vec<WindowButton>) -> Self {
+        Self { buttons }
+    }
+}
 
 impl<State: 'static> View<State> for WindowControls {
     type Element = widget::WindowControls<State>;
     fn build(&self) -> Self::Element {
-        widget::WindowControls::new()
+        widget::WindowControls::new(&self.buttons)
     }
 
-    fn rebuild(&self, _: &Self, _: &mut Self::Element) {}
+    fn rebuild(&self, old: &Self, element: &mut Self::Element) {
+        if self.buttons != old.buttons {
+            element.set_buttons(&self.buttons);
+        }
+    }
 
     fn teardown(&self, _: &mut Self::Element) {}
 }
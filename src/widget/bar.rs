@@ -1,5 +1,6 @@
 use crate::event;
 use crate::widget::{Widget, WidgetMarker};
+use crate::Palette;
 use parley::FontContext;
 use vello::kurbo::{Point, Size};
 
@@ -44,13 +45,18 @@ impl<State: 'static> Widget<State> for Bar<State> {
     fn debug_name(&self) -> &str {
         "bar"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         for child in self.children_mut() {
-            child.paint(scene);
+            child.paint(scene, palette);
         }
     }
 
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         if !suggested_size.is_finite() {
             panic!("FIXME: size is infinite");
         }
@@ -61,12 +67,12 @@ impl<State: 'static> Widget<State> for Bar<State> {
         let left_width;
         let right_width;
         if let Some(left) = &mut self.left {
-            left_width = left.layout(side_size_hint, font_context).width;
+            left_width = left.layout(side_size_hint, font_context, palette).width;
         } else {
             left_width = 0.;
         }
         if let Some(right) = &mut self.right {
-            right_width = right.layout(side_size_hint, font_context).width;
+            right_width = right.layout(side_size_hint, font_context, palette).width;
         } else {
             right_width = 0.;
         }
@@ -85,7 +91,7 @@ impl<State: 'static> Widget<State> for Bar<State> {
             height: self.height,
         };
         if let Some(middle) = &mut self.middle {
-            middle.layout(middle_size_hint, font_context);
+            middle.layout(middle_size_hint, font_context, palette);
             middle.size = Size::new(middle_width, self.height);
             middle.position = Point::new(max_width, 0.);
         }
@@ -1,32 +1,38 @@
+use crate::event::keyboard::{self, Key, NamedKey};
 use crate::event::{self, mouse::MouseButton};
 use crate::widget::{alignment, Container};
 use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::Task;
+use crate::{Padding, Palette};
 use parley::FontContext;
-use vello::peniko::kurbo::{Affine, Point, Rect, RoundedRectRadii, Size};
+use std::sync::Arc;
+use vello::peniko::kurbo::{Affine, Point, Rect, RoundedRectRadii, Size, Stroke};
 use vello::peniko::{Color, Fill};
 
 pub struct Button<State> {
     pub(crate) size: Size,
-    pub(crate) radii: RoundedRectRadii,
-    pub(crate) color: Color,
-    pub(crate) on_press: Option<Box<dyn Fn(&mut State) + Send + Sync + 'static>>,
+    /// Falls back to the radius and control colour of the palette
+    pub(crate) radii: Option<RoundedRectRadii>,
+    pub(crate) color: Option<Color>,
+    pub(crate) on_press: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
     child: Container<State>,
     hovered: bool,
+    focused: bool,
 }
 
 impl<State: 'static> Button<State> {
     pub fn new(
         child: impl Widget<State>,
         size: Size,
-        radii: RoundedRectRadii,
-        color: Color,
-        on_press: Option<Box<dyn Fn(&mut State) + Send + Sync + 'static>>,
+        radii: Option<RoundedRectRadii>,
+        color: Option<Color>,
+        on_press: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
     ) -> Self {
         let child = Container::new(
             child,
             alignment::Horizontal::Center,
             alignment::Vertical::Center,
-            0.0.into(),
+            Some(Padding::ZERO),
         );
         Self {
             size,
@@ -35,6 +41,7 @@ impl<State: 'static> Button<State> {
             on_press,
             child,
             hovered: false,
+            focused: false,
         }
     }
 }
@@ -44,24 +51,36 @@ impl<State: 'static> Widget<State> for Button<State> {
     fn debug_name(&self) -> &str {
         "button"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        let radii = self.radii.unwrap_or(palette.radius.into());
         scene.fill(
             Fill::NonZero,
             Affine::default(),
-            self.color,
+            self.color.unwrap_or(palette.control),
             None,
-            &Rect::from_origin_size(Point::new(0., 0.), self.size).to_rounded_rect(self.radii),
+            &Rect::from_origin_size(Point::new(0., 0.), self.size).to_rounded_rect(radii),
         );
         if self.hovered {
             scene.fill(
                 Fill::NonZero,
                 Affine::default(),
-                Color::BLACK.multiply_alpha(0.1),
+                palette.foreground.multiply_alpha(0.1),
                 None,
-                &Rect::from_origin_size(Point::new(0., 0.), self.size).to_rounded_rect(self.radii),
+                &Rect::from_origin_size(Point::new(0., 0.), self.size).to_rounded_rect(radii),
             );
         }
-        self.child.paint(scene);
+        if self.focused {
+            scene.stroke(
+                &Stroke::new(2.),
+                Affine::default(),
+                palette.accent.multiply_alpha(0.5),
+                None,
+                &Rect::from_origin_size(Point::new(0., 0.), self.size)
+                    .inset(-1.)
+                    .to_rounded_rect(radii),
+            );
+        }
+        self.child.paint(scene, palette);
     }
 
     fn children(&self) -> Vec<&WidgetData<State>> {
@@ -72,8 +91,8 @@ impl<State: 'static> Widget<State> for Button<State> {
         self.child.children_mut()
     }
 
-    fn layout(&mut self, _: Size, font_context: &mut FontContext) -> Size {
-        self.child.layout(self.size, font_context);
+    fn layout(&mut self, _: Size, font_context: &mut FontContext, palette: &Palette) -> Size {
+        self.child.layout(self.size, font_context, palette);
         self.size
     }
 
@@ -83,6 +102,21 @@ impl<State: 'static> Widget<State> for Button<State> {
         event: event::WidgetEvent,
         state: &mut State,
     ) -> event::Status {
+        if let event::WidgetEvent::Keyboard(keyboard::Event::KeyPressed {
+            key: Key::Named(NamedKey::Enter | NamedKey::Space),
+            repeat: false,
+            ..
+        }) = event
+        {
+            // without a handler the key is left to the parents
+            let Some(on_press) = &self.on_press else {
+                return event::Status::Ignored;
+            };
+            let task = (on_press)(state);
+            event_context.push_task(task);
+            event_context.state_changed = true;
+            return event::Status::Captured;
+        }
         if let event::WidgetEvent::Mouse(event::mouse::Event::Press {
             position: _,
             button,
@@ -90,7 +124,8 @@ impl<State: 'static> Widget<State> for Button<State> {
         {
             if button == MouseButton::Left {
                 if let Some(on_press) = &self.on_press {
-                    (on_press)(state);
+                    let task = (on_press)(state);
+                    event_context.push_task(task);
                     event_context.state_changed = true;
                 }
                 return event::Status::Captured;
@@ -102,4 +137,11 @@ impl<State: 'static> Widget<State> for Button<State> {
         self.hovered = hover;
         event::Status::Captured
     }
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+    fn set_focus(&mut self, focus: bool) -> event::Status {
+        self.focused = focus;
+        event::Status::Captured
+    }
 }
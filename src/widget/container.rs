@@ -1,5 +1,5 @@
 use crate::widget::{Widget, WidgetData, WidgetMarker};
-use crate::{event, Padding};
+use crate::{event, Padding, Palette};
 use parley::FontContext;
 use vello::kurbo::{Point, Size};
 
@@ -22,7 +22,8 @@ pub struct Container<State> {
     size: Size,
     pub(crate) h_alignment: alignment::Horizontal,
     pub(crate) v_alignment: alignment::Vertical,
-    pub(crate) padding: Padding,
+    /// `None` pads by [`Palette::spacing`]
+    pub(crate) padding: Option<Padding>,
     child: WidgetData<State>,
 }
 
@@ -31,7 +32,7 @@ impl<State: 'static> Container<State> {
         child: impl Widget<State>,
         h_alignment: alignment::Horizontal,
         v_alignment: alignment::Vertical,
-        padding: Padding,
+        padding: Option<Padding>,
     ) -> Self {
         Container {
             size: Size::ZERO,
@@ -45,8 +46,8 @@ impl<State: 'static> Container<State> {
 
 impl<State> WidgetMarker for Container<State> {}
 impl<State: 'static> Widget<State> for Container<State> {
-    fn paint(&mut self, scene: &mut vello::Scene) {
-        self.child.paint(scene);
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        self.child.paint(scene, palette);
     }
     fn debug_name(&self) -> &str {
         "container"
@@ -57,20 +58,28 @@ impl<State: 'static> Widget<State> for Container<State> {
     fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
         vec![&mut self.child]
     }
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         if !suggested_size.is_finite() {
             panic!("FIXME: size is infinite");
         }
         self.size = suggested_size;
+        let padding = self
+            .padding
+            .unwrap_or_else(|| Padding::new(palette.spacing));
         self.child.size = self.child.layout(
             Size::new(
-                self.size.width - self.padding.horizontal(),
-                self.size.height - self.padding.vertical(),
+                self.size.width - padding.horizontal(),
+                self.size.height - padding.vertical(),
             ),
             font_context,
+            palette,
         );
 
-        let padding = self.padding;
         let x = match self.h_alignment {
             alignment::Horizontal::Left => padding.left,
             alignment::Horizontal::Center => {
@@ -1,5 +1,6 @@
 use crate::event;
 use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::Palette;
 use parley::FontContext;
 use vello::kurbo::{Point, Size};
 
@@ -83,9 +84,9 @@ impl<State: 'static> Widget<State> for Flex<State> {
     fn debug_name(&self) -> &str {
         "flexbox"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         for child in self.children_mut() {
-            child.paint(scene);
+            child.paint(scene, palette);
         }
     }
 
@@ -100,7 +101,12 @@ impl<State: 'static> Widget<State> for Flex<State> {
             .collect()
     }
 
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         if !suggested_size.is_finite() {
             panic!("FIXME: size is infinite");
         }
@@ -114,7 +120,7 @@ impl<State: 'static> Widget<State> for Flex<State> {
         let mut total_flex_main_axis = 0.;
         let mut no_flex_children = true;
         for child in self.children.iter_mut() {
-            let child_size = child.widget.layout(suggested_size, font_context);
+            let child_size = child.widget.layout(suggested_size, font_context, palette);
             sizes.push(child_size);
             match self.main_axis {
                 FlexAxis::Horizontal => match child.flex_factor {
@@ -137,6 +143,8 @@ impl<State: 'static> Widget<State> for Flex<State> {
                 },
             }
         }
+        // the gaps between children are fixed space as well
+        total_fixed_main_axis += palette.spacing * self.children.len().saturating_sub(1) as f64;
         let flexible_space = main_axis_size - total_fixed_main_axis;
         let flex_unit_main_axis = flexible_space / total_flex_main_axis as f64;
         let justify_space = if no_flex_children {
@@ -223,13 +231,13 @@ impl<State: 'static> Widget<State> for Flex<State> {
                 }
             }
             child.widget.size = child_size;
-            child.widget.layout(child_size, font_context);
+            child.widget.layout(child_size, font_context, palette);
             match self.main_axis {
                 FlexAxis::Horizontal => {
-                    main_axis_off += child_size.width + justify_space;
+                    main_axis_off += child_size.width + justify_space + palette.spacing;
                 }
                 FlexAxis::Vertical => {
-                    main_axis_off += child_size.height + justify_space;
+                    main_axis_off += child_size.height + justify_space + palette.spacing;
                 }
             }
         }
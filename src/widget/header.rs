@@ -1,17 +1,26 @@
 use crate::event::{self, mouse::MouseButton, WidgetEvent};
 use crate::widget::{Widget, WidgetData, WidgetMarker, WindowControls};
+use crate::InternalMessage;
+use crate::Palette;
 use parley::FontContext;
+use std::time::{Duration, Instant};
 use vello::kurbo::{Point, Size};
-const WINDOW_CONTROLS_WIDTH: f64 = 100.;
 const HEADER_HEIGHT: f64 = 46.;
+// same defaults as GTK
+pub(crate) const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+pub(crate) const DOUBLE_CLICK_DISTANCE: f64 = 5.;
 
 /// like bar but includes window controls (for example minimise, maximise, close)
+/// Presses on empty space move the window, double-clicks toggle maximize, middle-clicks
+/// lower it and right-clicks open the window menu
 pub struct Header<State> {
     width: f64,
     pub left: WidgetData<State>,
     pub middle: WidgetData<State>,
     pub right: WidgetData<State>,
-    pub window_controls: WidgetData<State>,
+    pub start_controls: WidgetData<State>,
+    pub end_controls: WidgetData<State>,
+    last_click: Option<(Instant, Point)>,
 }
 
 #[allow(dead_code)]
@@ -20,14 +29,17 @@ impl<State: 'static> Header<State> {
         left: LW,
         middle: MW,
         right: RW,
-        window_controls: WindowControls<State>,
+        start_controls: WindowControls<State>,
+        end_controls: WindowControls<State>,
     ) -> Self {
         Self {
             width: 0.,
             left: WidgetData::new(Box::new(left)),
             middle: WidgetData::new(Box::new(middle)),
             right: WidgetData::new(Box::new(right)),
-            window_controls: WidgetData::new(Box::new(window_controls)),
+            start_controls: WidgetData::new(Box::new(start_controls)),
+            end_controls: WidgetData::new(Box::new(end_controls)),
+            last_click: None,
         }
     }
 }
@@ -37,26 +49,38 @@ impl<State: 'static> Widget<State> for Header<State> {
     fn debug_name(&self) -> &str {
         "header"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         for child in self.children_mut() {
-            child.paint(scene);
+            child.paint(scene, palette);
         }
     }
 
     fn children(&self) -> Vec<&WidgetData<State>> {
-        vec![&self.left, &self.middle, &self.right, &self.window_controls]
+        vec![
+            &self.start_controls,
+            &self.left,
+            &self.middle,
+            &self.right,
+            &self.end_controls,
+        ]
     }
 
     fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
         vec![
+            &mut self.start_controls,
             &mut self.left,
             &mut self.middle,
             &mut self.right,
-            &mut self.window_controls,
+            &mut self.end_controls,
         ]
     }
 
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         if !suggested_size.is_finite() {
             panic!("FIXME: size is infinite");
         }
@@ -65,28 +89,47 @@ impl<State: 'static> Widget<State> for Header<State> {
             width: f64::INFINITY,
             height: HEADER_HEIGHT,
         };
-        let left_width = self.left.layout(side_size_hint, font_context).width;
-        let right_width = self.right.layout(side_size_hint, font_context).width;
-        let max_width = f64::max(left_width, right_width + WINDOW_CONTROLS_WIDTH);
-        let middle_width = suggested_size.width - max_width * 2.;
-        self.left.size = Size::new(max_width, HEADER_HEIGHT);
-        self.left.position = Point::new(0., 0.);
-        self.right.size = Size::new(max_width, HEADER_HEIGHT);
-        self.right.position = Point::new(max_width + middle_width, 0.);
+        let start_controls_width = self
+            .start_controls
+            .layout(side_size_hint, font_context, palette)
+            .width;
+        let end_controls_width = self
+            .end_controls
+            .layout(side_size_hint, font_context, palette)
+            .width;
+        let left_width = self
+            .left
+            .layout(side_size_hint, font_context, palette)
+            .width;
+        let right_width = self
+            .right
+            .layout(side_size_hint, font_context, palette)
+            .width;
+        // the header is padded at its edges and between the sides and the middle,
+        // both sides get the same width so the middle stays centered
+        let spacing = palette.spacing;
+        let max_width = f64::max(
+            start_controls_width + left_width,
+            right_width + end_controls_width,
+        ) + spacing;
+        let middle_width = (suggested_size.width - (max_width + spacing) * 2.).max(0.);
+        self.start_controls.size = Size::new(start_controls_width, HEADER_HEIGHT);
+        self.start_controls.position = Point::new(spacing, 0.);
+        self.left.size = Size::new(max_width - spacing - start_controls_width, HEADER_HEIGHT);
+        self.left.position = Point::new(spacing + start_controls_width, 0.);
+        self.right.size = Size::new(max_width - spacing - end_controls_width, HEADER_HEIGHT);
+        self.right.position = Point::new(self.width - max_width, 0.);
+        self.end_controls.size = Size::new(end_controls_width, HEADER_HEIGHT);
+        self.end_controls.position = Point::new(self.width - spacing - end_controls_width, 0.);
         let middle_size_suggestion = Size {
             width: middle_width,
             height: HEADER_HEIGHT,
         };
         // TODO: handle all sizes within constraints
-        self.middle.layout(middle_size_suggestion, font_context);
+        self.middle
+            .layout(middle_size_suggestion, font_context, palette);
         self.middle.size = Size::new(middle_width, HEADER_HEIGHT);
-        self.middle.position = Point::new(max_width, 0.);
-        self.window_controls.inner.layout(
-            Size::new(WINDOW_CONTROLS_WIDTH, HEADER_HEIGHT),
-            font_context,
-        );
-        self.window_controls.position = Point::new(self.width - WINDOW_CONTROLS_WIDTH, 0.);
-        self.window_controls.size = Size::new(WINDOW_CONTROLS_WIDTH, HEADER_HEIGHT);
+        self.middle.position = Point::new(max_width + spacing, 0.);
 
         suggested_size
     }
@@ -97,13 +140,21 @@ impl<State: 'static> Widget<State> for Header<State> {
         event: event::WidgetEvent,
         _state: &mut State,
     ) -> event::Status {
-        if let WidgetEvent::Mouse(event::mouse::Event::Press {
-            position: _,
-            button: MouseButton::Left,
-        }) = event.clone()
-        {
-            let _ = event_context.winit_window.drag_window();
-            return event::Status::Captured;
+        if let WidgetEvent::Mouse(event::mouse::Event::Press { position, button }) = event {
+            let now = Instant::now();
+            let double_click = button == MouseButton::Left
+                && self.last_click.is_some_and(|(time, last_position)| {
+                    now - time <= DOUBLE_CLICK_TIME
+                        && (position - last_position).hypot() <= DOUBLE_CLICK_DISTANCE
+                });
+            self.last_click =
+                (button == MouseButton::Left && !double_click).then_some((now, position));
+            // widgets inside the header get the press after the header, so the window
+            // decides what to do once it knows whether one of them captured it
+            event_context.push_internal_message(InternalMessage::HeaderPressed {
+                button,
+                double_click,
+            });
         }
         event::Status::Ignored
     }
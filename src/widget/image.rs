@@ -1,5 +1,6 @@
 use crate::event;
 use crate::widget::{Widget, WidgetMarker};
+use crate::Palette;
 use image::ImageReader;
 use parley::FontContext;
 use std::io::Cursor;
@@ -38,14 +39,14 @@ impl Image {
 
 impl WidgetMarker for Image {}
 impl<State: 'static> Widget<State> for Image {
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, _palette: &Palette) {
         scene.draw_image(&self.image.clone(), Affine::default());
     }
 
     fn debug_name(&self) -> &str {
         "image"
     }
-    fn layout(&mut self, _: Size, _font_context: &mut FontContext) -> Size {
+    fn layout(&mut self, _: Size, _font_context: &mut FontContext, _palette: &Palette) -> Size {
         self.size
     }
 
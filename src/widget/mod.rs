@@ -10,16 +10,25 @@ mod header;
 pub use header::*;
 mod image;
 pub use image::*;
+mod rich_text;
+pub use rich_text::*;
 mod root_widget;
 pub use root_widget::*;
 mod scroll;
 pub use scroll::*;
 mod slider;
 pub use slider::*;
+mod styled;
+pub use styled::*;
 mod svg;
 pub use svg::*;
 mod text;
 pub use text::*;
+mod text_edit;
+mod text_editor;
+pub use text_editor::*;
+mod text_input;
+pub use text_input::*;
 mod widget;
 pub use widget::*;
 mod window;
@@ -0,0 +1,256 @@
+use crate::event::{self, mouse, mouse::MouseButton, WidgetEvent};
+use crate::widget::text::{build_styled_layout, paint_layout, FontWeight, TextAlign, TextStyle};
+use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::Palette;
+use crate::Task;
+use parley::layout::Cursor;
+use parley::style::{FontStyle, StyleProperty};
+use parley::{FontContext, Layout};
+use std::ops::Range;
+use std::sync::Arc;
+use vello::peniko::kurbo::{Affine, Point, Size};
+use vello::peniko::{Brush, Color};
+use winit::window::CursorIcon;
+
+/// Style of one run of a [`RichText`], unset fields fall back to the paragraph style
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpanStyle {
+    pub weight: Option<FontWeight>,
+    /// Spans with an `on_click` fall back to the accent colour of the palette
+    pub color: Option<Color>,
+    pub size: Option<f32>,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+pub struct TextSpan<State> {
+    pub(crate) text: String,
+    pub(crate) style: SpanStyle,
+    pub(crate) on_click: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
+}
+
+impl<State> TextSpan<State> {
+    pub fn new(
+        text: String,
+        style: SpanStyle,
+        on_click: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            text,
+            style,
+            on_click,
+        }
+    }
+}
+
+/// One paragraph made of differently styled spans, spans with an `on_click` act as links.
+/// Rebuilds that leave the text and style of every span alone keep the layout, otherwise
+/// the whole paragraph is laid out again, see [`RichText::update_span`]
+pub struct RichText<State> {
+    spans: Vec<TextSpan<State>>,
+    pub(crate) style: TextStyle,
+    /// Byte range of every span in the concatenated text
+    ranges: Vec<Range<usize>>,
+    layout: Layout<Brush>,
+    /// Width and palette the current layout was made for, `None` once a span changed
+    laid_out_for: Option<(f64, Palette)>,
+}
+
+impl<State: 'static> RichText<State> {
+    pub fn new(spans: Vec<TextSpan<State>>, style: TextStyle) -> Self {
+        Self {
+            spans,
+            style,
+            ranges: vec![],
+            layout: Layout::new(),
+            laid_out_for: None,
+        }
+    }
+    pub fn span_count(&self) -> usize {
+        self.spans.len()
+    }
+    /// Replaces the span at `idx`, which lays out the whole paragraph again.
+    ///
+    /// parley 0.2 has no way to reshape part of a layout: a `RangedBuilder` shapes the
+    /// concatenated text of every span in one go, so line breaking and bidi see the whole
+    /// paragraph, and the colour of a span is baked into its glyph runs as the brush.
+    /// Even a colour only change therefore means a full relayout.
+    // TODO: Needs sign-off, either accept full relayouts of changed paragraphs or paint
+    // brushes outside the layout so colour changes skip shaping
+    pub fn update_span(&mut self, idx: usize, span: TextSpan<State>) {
+        self.spans[idx] = span;
+        self.laid_out_for = None;
+    }
+    /// Swaps the click handler of the span at `idx`, the layout is kept unless the
+    /// span turns into a link or stops being one, since links get the accent colour
+    pub fn set_span_on_click(
+        &mut self,
+        idx: usize,
+        on_click: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
+    ) {
+        let span = &mut self.spans[idx];
+        if span.on_click.is_some() != on_click.is_some() {
+            self.laid_out_for = None;
+        }
+        span.on_click = on_click;
+    }
+    pub fn push_span(&mut self, span: TextSpan<State>) {
+        self.spans.push(span);
+        self.laid_out_for = None;
+    }
+    pub fn truncate_spans(&mut self, len: usize) {
+        if len < self.spans.len() {
+            self.spans.truncate(len);
+            self.laid_out_for = None;
+        }
+    }
+    pub(crate) fn invalidate_layout(&mut self) {
+        self.laid_out_for = None;
+    }
+
+    fn ranged_properties(
+        &self,
+        palette: &Palette,
+    ) -> Vec<(StyleProperty<'static, Brush>, Range<usize>)> {
+        let mut properties = vec![];
+        for (span, range) in self.spans.iter().zip(&self.ranges) {
+            let style = &span.style;
+            let color = match (style.color, &span.on_click) {
+                (Some(color), _) => Some(color),
+                (None, Some(_)) => Some(palette.accent),
+                (None, None) => None,
+            };
+            if let Some(color) = color {
+                properties.push((StyleProperty::Brush(Brush::Solid(color)), range.clone()));
+            }
+            if let Some(weight) = style.weight {
+                properties.push((StyleProperty::FontWeight(weight), range.clone()));
+            }
+            if let Some(size) = style.size {
+                properties.push((StyleProperty::FontSize(size), range.clone()));
+            }
+            if style.italic {
+                properties.push((StyleProperty::FontStyle(FontStyle::Italic), range.clone()));
+            }
+            if style.underline {
+                properties.push((StyleProperty::Underline(true), range.clone()));
+            }
+            if style.strikethrough {
+                properties.push((StyleProperty::Strikethrough(true), range.clone()));
+            }
+        }
+        properties
+    }
+
+    /// Index of the clickable span under `position`
+    fn link_at(&self, position: Point) -> Option<usize> {
+        if position.x < 0.
+            || position.y < 0.
+            || position.x > self.layout.width() as f64
+            || position.y > self.layout.height() as f64
+        {
+            return None;
+        }
+        let idx = Cursor::from_point(&self.layout, position.x as f32, position.y as f32).text_start;
+        self.ranges
+            .iter()
+            .position(|range| range.contains(&idx))
+            .filter(|span| self.spans[*span].on_click.is_some())
+    }
+}
+
+impl<State> WidgetMarker for RichText<State> {}
+impl<State: 'static> Widget<State> for RichText<State> {
+    fn debug_name(&self) -> &str {
+        "rich_text"
+    }
+    fn paint(&mut self, scene: &mut vello::Scene, _palette: &Palette) {
+        paint_layout(scene, &self.layout, Affine::default());
+    }
+
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
+        let up_to_date = self
+            .laid_out_for
+            .as_ref()
+            .is_some_and(|(width, old_palette)| {
+                *width == suggested_size.width && old_palette == palette
+            });
+        if !up_to_date {
+            let mut text = String::new();
+            self.ranges.clear();
+            for span in &self.spans {
+                let start = text.len();
+                text.push_str(&span.text);
+                self.ranges.push(start..text.len());
+            }
+            let mut layout = build_styled_layout(
+                font_context,
+                palette,
+                &text,
+                &self.style,
+                Brush::Solid(self.style.color.unwrap_or(palette.foreground)),
+                &self.ranged_properties(palette),
+            );
+            layout.break_all_lines(Some(suggested_size.width as f32));
+            layout.align(Some(suggested_size.width as f32), self.style.align.into());
+            self.layout = layout;
+            self.laid_out_for = Some((suggested_size.width, palette.clone()));
+        }
+        let width = if self.style.align != TextAlign::Start && suggested_size.width.is_finite() {
+            suggested_size.width
+        } else {
+            self.layout.width() as f64
+        };
+        Size::new(width, self.layout.height() as f64)
+    }
+
+    fn children(&self) -> Vec<&WidgetData<State>> {
+        vec![]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
+        vec![]
+    }
+
+    fn event(
+        &mut self,
+        event_context: &mut event::EventContext,
+        event: event::WidgetEvent,
+        state: &mut State,
+    ) -> event::Status {
+        match event {
+            WidgetEvent::Mouse(mouse::Event::Move { position }) => {
+                if self.link_at(position).is_some() {
+                    event_context.set_cursor(CursorIcon::Pointer);
+                    return event::Status::Captured;
+                }
+                event::Status::Ignored
+            }
+            WidgetEvent::Mouse(mouse::Event::Press {
+                position,
+                button: MouseButton::Left,
+            }) => {
+                let Some(span) = self.link_at(position) else {
+                    return event::Status::Ignored;
+                };
+                if let Some(on_click) = &self.spans[span].on_click {
+                    let task = (on_click)(state);
+                    event_context.push_task(task);
+                    event_context.state_changed = true;
+                }
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn set_hover(&mut self, _hover: bool) -> event::Status {
+        event::Status::Ignored
+    }
+}
@@ -1,7 +1,8 @@
 use crate::event::{self, EventContext, WidgetEvent};
 use crate::widget::{Widget, WidgetData, WidgetIdPath, WidgetMarker};
+use crate::Palette;
 use parley::FontContext;
-use vello::peniko::kurbo::{Point, Size};
+use vello::peniko::kurbo::{Point, Rect, Size};
 
 pub struct RootWidget<State> {
     child: WidgetData<State>,
@@ -24,10 +25,11 @@ impl<State: 'static> RootWidget<State> {
         event_context: &mut EventContext,
         mut id_path: WidgetIdPath,
         state: &mut State,
-    ) {
+    ) -> event::Status {
         // let mut widget_events = Vec::with_capacity(id_path.len());
-        self.child.inner.event(event_context, event.clone(), state);
-        _ = id_path.remove(0); // skip RootWidget's child
+        let mut status = self.child.inner.event(event_context, event.clone(), state);
+        let mut current_path = vec![id_path.remove(0)]; // skip RootWidget's child
+        event_context.resolve_focus_request(&current_path);
         let mut widget = &mut self.child;
         for id in id_path {
             let child = match widget
@@ -39,14 +41,19 @@ impl<State: 'static> RootWidget<State> {
                 Some(child) => child,
                 None => {
                     tracing::warn!("Tried to send {event:?} to stale widget with id: {id}");
-                    return;
+                    return status;
                 }
             };
 
             event = event::widget_event(event.clone(), child.position);
-            child.inner.event(event_context, event.clone(), state);
+            if child.inner.event(event_context, event.clone(), state) == event::Status::Captured {
+                status = event::Status::Captured;
+            }
+            current_path.push(id);
+            event_context.resolve_focus_request(&current_path);
             widget = child;
         }
+        status
     }
     pub fn send_hover(&mut self, hover: bool, mut id_path: WidgetIdPath) {
         self.child.inner.set_hover(hover);
@@ -71,14 +78,78 @@ impl<State: 'static> RootWidget<State> {
             widget = child;
         }
     }
+    pub fn send_focus(&mut self, focus: bool, mut id_path: WidgetIdPath) {
+        _ = id_path.remove(0); // skip RootWidget's child
+        let mut widget = &mut self.child;
+        for id in id_path {
+            widget = match widget
+                .inner
+                .children_mut()
+                .into_iter()
+                .find(|widget| widget.id == id)
+            {
+                Some(widget) => widget,
+                None => {
+                    tracing::warn!("Tried to send focus={focus} to stale widget with id: {id}");
+                    return;
+                }
+            };
+        }
+        widget.inner.set_focus(focus);
+    }
+
+    /// IME cursor area of the widget at `id_path`, in window coordinates
+    pub fn ime_cursor_area(&self, mut id_path: WidgetIdPath) -> Option<Rect> {
+        _ = id_path.remove(0); // skip RootWidget's child
+        let mut widget = &self.child;
+        let mut origin = widget.position;
+        for id in id_path {
+            widget = widget
+                .inner
+                .children()
+                .into_iter()
+                .find(|widget| widget.id == id)?;
+            origin += widget.position.to_vec2();
+        }
+        widget
+            .inner
+            .ime_cursor_area()
+            .map(|area| area + origin.to_vec2())
+    }
+
+    /// Id paths of every widget that accepts focus, in tree order
+    pub fn focus_chain(&self) -> Vec<WidgetIdPath> {
+        fn collect<State: 'static>(
+            widget: &WidgetData<State>,
+            id_path: WidgetIdPath,
+            chain: &mut Vec<WidgetIdPath>,
+        ) {
+            if widget.inner.accepts_focus() {
+                chain.push(id_path.clone());
+            }
+            for child in widget.inner.children() {
+                let mut child_id_path = id_path.clone();
+                child_id_path.push(child.id);
+                collect(child, child_id_path, chain);
+            }
+        }
+        let mut chain = vec![];
+        collect(&self.child, vec![self.child.id], &mut chain);
+        chain
+    }
 }
 
 impl<State> WidgetMarker for RootWidget<State> {}
 impl<State: 'static> Widget<State> for RootWidget<State> {
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         self.child.position = Point::ZERO;
         self.child.size = suggested_size;
-        self.child.layout(suggested_size, font_context);
+        self.child.layout(suggested_size, font_context, palette);
         suggested_size
     }
     fn event(
@@ -92,8 +163,8 @@ impl<State: 'static> Widget<State> for RootWidget<State> {
     fn set_hover(&mut self, _hover: bool) -> event::Status {
         event::Status::Ignored
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
-        self.child.paint(scene)
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        self.child.paint(scene, palette)
     }
     fn children(&self) -> Vec<&WidgetData<State>> {
         vec![&self.child]
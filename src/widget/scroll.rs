@@ -2,6 +2,7 @@ use core::f64;
 
 use crate::event;
 use crate::widget::{Widget, WidgetMarker};
+use crate::Palette;
 
 use parley::FontContext;
 use vello::{
@@ -29,14 +30,14 @@ impl<State> Scroll<State> {
 
 impl<State> WidgetMarker for Scroll<State> {}
 impl<State: 'static> Widget<State> for Scroll<State> {
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         scene.push_layer(
             BlendMode::default(),
             1.0,
             Affine::default(),
             &self.size.to_rect(),
         );
-        self.child.paint(scene);
+        self.child.paint(scene, palette);
         scene.pop_layer();
         scene.fill(
             vello::peniko::Fill::NonZero,
@@ -70,14 +71,21 @@ impl<State: 'static> Widget<State> for Scroll<State> {
     fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
         vec![&mut self.child]
     }
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         if !suggested_size.is_finite() {
             panic!("FIXME: size is infinite");
         }
         self.size = suggested_size;
-        self.child.size = self
-            .child
-            .layout(Size::new(f64::INFINITY, f64::INFINITY), font_context);
+        self.child.size = self.child.layout(
+            Size::new(f64::INFINITY, f64::INFINITY),
+            font_context,
+            palette,
+        );
         self.child.position = Point::new(
             (self.child.size.width - self.size.width) * self.scroll.x,
             (self.child.size.height - self.size.height) * self.scroll.y,
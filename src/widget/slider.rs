@@ -1,27 +1,33 @@
 use crate::event::{
     self,
+    keyboard::{self, Key, NamedKey},
     mouse::{self, MouseButton},
 };
 use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::Palette;
+use crate::Task;
 use parley::FontContext;
+use std::sync::Arc;
 use vello::kurbo::Affine;
-use vello::peniko::kurbo::{Circle, Point, Rect, Size};
-use vello::peniko::{Color, Fill};
+use vello::peniko::kurbo::{Circle, Point, Rect, Size, Stroke};
+use vello::peniko::Fill;
 
 const SLIDER_HEIGHT: f64 = 50.;
+const KEYBOARD_STEP: f64 = 0.05;
 pub struct Slider<State> {
     // TODO: Support vertical sliders
     length: f64,
     pub(crate) value: f64, // from 0.0 to 1.0
-    pub(crate) on_change: Box<dyn Fn(&mut State, f64) + Send + Sync + 'static>,
+    pub(crate) on_change: Arc<dyn Fn(&mut State, f64) -> Task<State> + Send + Sync + 'static>,
     is_dragging: bool,
     hovered: bool,
+    focused: bool,
 }
 
 impl<State> Slider<State> {
     pub fn new(
         value: f64,
-        on_change: Box<dyn Fn(&mut State, f64) + Send + Sync + 'static>,
+        on_change: Arc<dyn Fn(&mut State, f64) -> Task<State> + Send + Sync + 'static>,
     ) -> Self {
         Self {
             length: 0.,
@@ -29,6 +35,7 @@ impl<State> Slider<State> {
             on_change,
             is_dragging: false,
             hovered: false,
+            focused: false,
         }
     }
 }
@@ -38,11 +45,11 @@ impl<State: 'static> Widget<State> for Slider<State> {
     fn debug_name(&self) -> &str {
         "slider"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         scene.fill(
             Fill::NonZero,
             Affine::default(),
-            Color::LIGHT_GRAY,
+            palette.control,
             None,
             &Rect::from_origin_size(
                 Point::new(0., 0.),
@@ -53,7 +60,7 @@ impl<State: 'static> Widget<State> for Slider<State> {
         scene.fill(
             Fill::NonZero,
             Affine::default(),
-            Color::GRAY,
+            palette.handle,
             None,
             &Circle::new(
                 Point::new(self.value * self.length, SLIDER_HEIGHT / 4.),
@@ -64,7 +71,7 @@ impl<State: 'static> Widget<State> for Slider<State> {
             scene.fill(
                 Fill::NonZero,
                 Affine::default(),
-                Color::BLACK.multiply_alpha(0.3),
+                palette.foreground.multiply_alpha(0.3),
                 None,
                 &Circle::new(
                     Point::new(self.value * self.length, SLIDER_HEIGHT / 4.),
@@ -72,6 +79,18 @@ impl<State: 'static> Widget<State> for Slider<State> {
                 ),
             );
         }
+        if self.focused {
+            scene.stroke(
+                &Stroke::new(2.),
+                Affine::default(),
+                palette.accent.multiply_alpha(0.5),
+                None,
+                &Circle::new(
+                    Point::new(self.value * self.length, SLIDER_HEIGHT / 4.),
+                    SLIDER_HEIGHT / 2. + 1.,
+                ),
+            );
+        }
     }
 
     fn children(&self) -> Vec<&WidgetData<State>> {
@@ -82,7 +101,12 @@ impl<State: 'static> Widget<State> for Slider<State> {
         vec![]
     }
 
-    fn layout(&mut self, suggested_size: Size, _font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        _font_context: &mut FontContext,
+        _palette: &Palette,
+    ) -> Size {
         if !suggested_size.is_finite() {
             panic!("FIXME: size is infinite");
         }
@@ -96,11 +120,29 @@ impl<State: 'static> Widget<State> for Slider<State> {
         event: event::WidgetEvent,
         state: &mut State,
     ) -> event::Status {
+        if let event::WidgetEvent::Keyboard(keyboard::Event::KeyPressed {
+            key: Key::Named(named_key),
+            ..
+        }) = event
+        {
+            let value = match named_key {
+                NamedKey::ArrowLeft | NamedKey::ArrowDown => self.value - KEYBOARD_STEP,
+                NamedKey::ArrowRight | NamedKey::ArrowUp => self.value + KEYBOARD_STEP,
+                NamedKey::Home => 0.,
+                NamedKey::End => 1.,
+                _ => return event::Status::Ignored,
+            };
+            let task = (self.on_change)(state, value.clamp(0., 1.));
+            event_context.push_task(task);
+            event_context.state_changed = true;
+            return event::Status::Captured;
+        }
         if let event::WidgetEvent::Mouse(mouse_event) = event {
             match mouse_event {
                 mouse::Event::Move { position } => {
                     if self.is_dragging {
-                        (self.on_change)(state, position.x / self.length);
+                        let task = (self.on_change)(state, position.x / self.length);
+                        event_context.push_task(task);
                         event_context.state_changed = true;
                         // Changing self.value is done by the user
                     }
@@ -136,4 +178,11 @@ impl<State: 'static> Widget<State> for Slider<State> {
         self.hovered = hover;
         event::Status::Captured
     }
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+    fn set_focus(&mut self, focus: bool) -> event::Status {
+        self.focused = focus;
+        event::Status::Captured
+    }
 }
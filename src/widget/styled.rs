@@ -0,0 +1,150 @@
+use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::{event, Palette};
+use parley::FontContext;
+use std::borrow::Cow;
+use vello::peniko::kurbo::{Affine, Point, Rect, RoundedRectRadii, Size, Stroke};
+use vello::peniko::{BlendMode, Color, Fill};
+
+/// Decoration drawn around a widget by [`Styled`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    pub background: Option<Color>,
+    pub border_width: f64,
+    /// Falls back to the control colour of the palette
+    pub border_color: Option<Color>,
+    pub radii: RoundedRectRadii,
+    pub opacity: f32,
+    /// Replaces the foreground colour of the palette for the wrapped widget and its children
+    pub foreground: Option<Color>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            background: None,
+            border_width: 0.,
+            border_color: None,
+            radii: RoundedRectRadii::from_single_radius(0.),
+            opacity: 1.,
+            foreground: None,
+        }
+    }
+}
+
+/// Paints a background and border behind its child, which is inset by the border width
+pub struct Styled<State> {
+    size: Size,
+    pub(crate) style: Style,
+    child: WidgetData<State>,
+}
+
+impl<State: 'static> Styled<State> {
+    pub fn new(child: impl Widget<State>, style: Style) -> Self {
+        Self {
+            size: Size::ZERO,
+            style,
+            child: WidgetData::new(Box::new(child)),
+        }
+    }
+    fn child_palette<'a>(&self, palette: &'a Palette) -> Cow<'a, Palette> {
+        match self.style.foreground {
+            Some(foreground) => Cow::Owned(Palette {
+                foreground,
+                ..palette.clone()
+            }),
+            None => Cow::Borrowed(palette),
+        }
+    }
+}
+
+impl<State> WidgetMarker for Styled<State> {}
+impl<State: 'static> Widget<State> for Styled<State> {
+    fn debug_name(&self) -> &str {
+        "styled"
+    }
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        let bounds = Rect::from_origin_size(Point::ZERO, self.size);
+        let translucent = self.style.opacity < 1.;
+        if translucent {
+            scene.push_layer(
+                BlendMode::default(),
+                self.style.opacity,
+                Affine::default(),
+                &bounds,
+            );
+        }
+        if let Some(background) = self.style.background {
+            scene.fill(
+                Fill::NonZero,
+                Affine::default(),
+                background,
+                None,
+                &bounds.to_rounded_rect(self.style.radii),
+            );
+        }
+        let border_width = self.style.border_width;
+        if border_width > 0. {
+            // strokes are centered on the path, so move it inwards by half the width
+            scene.stroke(
+                &Stroke::new(border_width),
+                Affine::default(),
+                self.style.border_color.unwrap_or(palette.control),
+                None,
+                &bounds
+                    .inset(-border_width / 2.)
+                    .to_rounded_rect(self.style.radii),
+            );
+        }
+        let child_palette = self.child_palette(palette);
+        self.child.paint(scene, &child_palette);
+        if translucent {
+            scene.pop_layer();
+        }
+    }
+
+    fn children(&self) -> Vec<&WidgetData<State>> {
+        vec![&self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
+        vec![&mut self.child]
+    }
+
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
+        let border_width = self.style.border_width;
+        let child_palette = self.child_palette(palette);
+        let child_size = self.child.layout(
+            Size::new(
+                (suggested_size.width - border_width * 2.).max(0.),
+                (suggested_size.height - border_width * 2.).max(0.),
+            ),
+            font_context,
+            &child_palette,
+        );
+        self.child.size = child_size;
+        self.child.position = Point::new(border_width, border_width);
+        self.size = Size::new(
+            child_size.width + border_width * 2.,
+            child_size.height + border_width * 2.,
+        );
+        self.size
+    }
+
+    fn event(
+        &mut self,
+        _event_context: &mut event::EventContext,
+        _event: event::WidgetEvent,
+        _state: &mut State,
+    ) -> event::Status {
+        event::Status::Ignored
+    }
+
+    fn set_hover(&mut self, _hover: bool) -> event::Status {
+        event::Status::Ignored
+    }
+}
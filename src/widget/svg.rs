@@ -1,5 +1,6 @@
 use crate::event;
 use crate::widget::{Widget, WidgetMarker};
+use crate::Palette;
 use parley::FontContext;
 use vello::kurbo::{Affine, Size};
 use vello_svg::usvg;
@@ -24,7 +25,7 @@ impl Svg {
 
 impl WidgetMarker for Svg {}
 impl<State: 'static> Widget<State> for Svg {
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, _palette: &Palette) {
         let scale = Affine::scale_non_uniform(
             self.size.width / self.svg.size().width() as f64,
             self.size.height / self.svg.size().height() as f64,
@@ -36,7 +37,7 @@ impl<State: 'static> Widget<State> for Svg {
     fn debug_name(&self) -> &str {
         "svg"
     }
-    fn layout(&mut self, _: Size, _font_context: &mut FontContext) -> Size {
+    fn layout(&mut self, _: Size, _font_context: &mut FontContext, _palette: &Palette) -> Size {
         self.size
     }
 
@@ -1,50 +1,260 @@
 use std::borrow::Cow;
+use std::ops::Range;
+use std::time::Instant;
 
-use crate::event;
+use crate::event::keyboard::{self, Key};
+use crate::event::{self, mouse, mouse::MouseButton, WidgetEvent};
+use crate::widget::header::{DOUBLE_CLICK_DISTANCE, DOUBLE_CLICK_TIME};
+use crate::widget::text_edit::{word_range_at, Selection};
 use crate::widget::{Widget, WidgetMarker};
-use parley::{style::FontFamily, FontContext, Layout};
-use vello::peniko::kurbo::{Affine, Size};
-use vello::peniko::{Brush, Color};
+use crate::Palette;
+use parley::layout::{Cursor, Decoration, GlyphRun};
+pub use parley::style::FontWeight;
+use parley::style::{FontFamily, StyleProperty};
+use parley::{FontContext, Layout};
+use vello::peniko::kurbo::{Affine, Point, Rect, Size};
+use vello::peniko::{BlendMode, Brush, Color, Fill};
+use winit::window::CursorIcon;
+
+/// Horizontal placement of lines within the width the text was given
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Stretches every line but the last to the full width
+    Justify,
+}
+
+impl From<TextAlign> for parley::layout::Alignment {
+    fn from(align: TextAlign) -> Self {
+        match align {
+            TextAlign::Start => parley::layout::Alignment::Start,
+            TextAlign::Center => parley::layout::Alignment::Middle,
+            TextAlign::End => parley::layout::Alignment::End,
+            TextAlign::Justify => parley::layout::Alignment::Justified,
+        }
+    }
+}
+
+/// What a [`Text`] does with lines past its `max_lines` or wider than its width
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextOverflow {
+    /// Cuts the text off at the edge of the widget
+    #[default]
+    Clip,
+    /// Drops the end of the text and puts an ellipsis in its place
+    Ellipsis,
+    /// Keeps the start and end of the text with an ellipsis in between, for file paths
+    MiddleEllipsis,
+}
+
+/// How a [`Text`] is shaped, unset fields fall back to the palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    pub font_families: Option<Vec<String>>,
+    pub font_size: Option<f32>,
+    pub weight: FontWeight,
+    pub italic: bool,
+    pub color: Option<Color>,
+    /// Extra space between characters, in logical pixels
+    pub letter_spacing: f32,
+    /// Multiple of the font size
+    pub line_height: f32,
+    pub align: TextAlign,
+    pub max_lines: Option<usize>,
+    pub overflow: TextOverflow,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font_families: None,
+            font_size: None,
+            weight: FontWeight::NORMAL,
+            italic: false,
+            color: None,
+            letter_spacing: 0.,
+            line_height: 1.,
+            align: TextAlign::Start,
+            max_lines: None,
+            overflow: TextOverflow::Clip,
+        }
+    }
+}
+
 pub struct Text {
     text: String,
+    pub(crate) style: TextStyle,
     layout: Layout<Brush>,
+    /// Size to clip painting to, when the text overflows with [`TextOverflow::Clip`]
+    clip: Option<Size>,
+    /// The shortened text shown when the text overflows with an ellipsis
+    ellipsized: Option<String>,
+    /// Text, style, width and palette the current layout was made for, finding where
+    /// to put an ellipsis shapes the text several times so it's only done when they change
+    laid_out_for: Option<(String, TextStyle, f64, Palette)>,
+    selectable: bool,
+    /// Byte indices into the shown text
+    selection: Selection,
+    is_dragging: bool,
+    /// Time, position and count of the last clicks, to tell double and triple clicks apart
+    last_click: Option<(Instant, Point, u8)>,
 }
 
 impl Text {
-    pub fn new(text: String) -> Self {
+    pub fn new(text: String, style: TextStyle) -> Self {
         Self {
             text: text.clone(),
+            style,
             layout: Layout::new(),
+            clip: None,
+            ellipsized: None,
+            laid_out_for: None,
+            selectable: false,
+            selection: Selection::default(),
+            is_dragging: false,
+            last_click: None,
         }
     }
 
-    pub fn layout_text(&mut self, text: String, size: Size, font_context: &mut FontContext) {
+    pub fn layout_text(
+        &mut self,
+        text: String,
+        size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) {
+        let up_to_date =
+            self.laid_out_for
+                .as_ref()
+                .is_some_and(|(old_text, style, width, old_palette)| {
+                    *old_text == text
+                        && *style == self.style
+                        && *width == size.width
+                        && old_palette == palette
+                });
         self.text = text;
-        let mut lcx = parley::LayoutContext::new();
-        let mut layout_builder = lcx.ranged_builder(font_context, &self.text, 1.0);
-        layout_builder.push_default(parley::style::StyleProperty::Brush(Brush::Solid(
-            Color::BLACK,
-        )));
-        layout_builder.push_default(parley::style::StyleProperty::FontStack(
-            parley::style::FontStack::List(Cow::Borrowed(&[
-                FontFamily::Named(Cow::Borrowed("Inter")),
-                FontFamily::Named(Cow::Borrowed("Noto Sans")),
-            ])),
+        if up_to_date {
+            return;
+        }
+        self.clip = None;
+        self.ellipsized = None;
+        self.lay_out_shown_text(size, font_context, palette);
+        let shown_text = self.ellipsized.as_deref().unwrap_or(&self.text);
+        self.selection.clamp(shown_text);
+        self.laid_out_for = Some((
+            self.text.clone(),
+            self.style.clone(),
+            size.width,
+            palette.clone(),
         ));
-        layout_builder.push_default(parley::style::StyleProperty::FontSize(14.6666666));
-        layout_builder.push_default(parley::style::StyleProperty::Brush(Brush::Solid(
-            Color::BLACK,
-        )));
-        // layout_builder.push_default(&parley::style::StyleProperty::FontStyle(
-        //     parley::style::FontStyle::Italic,
-        // ));
-        // layout_builder.push_default(&parley::style::StyleProperty::FontWeight(
-        //     parley::style::FontWeight::BOLD,
-        // ));
-        let mut layout = layout_builder.build(&self.text);
-        layout.break_all_lines(Some(size.width as f32));
-        layout.align(Some(size.width as f32), parley::layout::Alignment::Start);
-        self.layout = layout;
+    }
+    fn lay_out_shown_text(
+        &mut self,
+        size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) {
+        let layout = self.shape(&self.text, size.width, font_context, palette);
+        if self.fits(&layout, size.width) {
+            self.layout = layout;
+            return;
+        }
+        match self.style.overflow {
+            TextOverflow::Clip => {
+                let max_lines = self.style.max_lines.unwrap_or(usize::MAX);
+                let height = match max_lines.checked_sub(1).and_then(|last| layout.get(last)) {
+                    Some(line) => {
+                        let metrics = line.metrics();
+                        (metrics.baseline + metrics.descent + metrics.leading * 0.5) as f64
+                    }
+                    None => layout.height() as f64,
+                };
+                self.clip = Some(Size::new(
+                    (layout.width() as f64).min(size.width),
+                    height.min(layout.height() as f64),
+                ));
+                self.layout = layout;
+            }
+            TextOverflow::Ellipsis | TextOverflow::MiddleEllipsis => {
+                // keep as many characters as still fit once the ellipsis is added
+                let boundaries = char_boundaries(&self.text);
+                let char_count = boundaries.len() - 1;
+                let mut low = 0;
+                let mut high = char_count;
+                let mut best = None;
+                while low < high {
+                    let mid = (low + high + 1) / 2;
+                    let candidate = self.shape(
+                        &ellipsized(&self.text, &boundaries, mid, self.style.overflow),
+                        size.width,
+                        font_context,
+                        palette,
+                    );
+                    if self.fits(&candidate, size.width) {
+                        low = mid;
+                        best = Some(candidate);
+                    } else {
+                        high = mid - 1;
+                    }
+                }
+                let kept = low;
+                let ellipsized = ellipsized(&self.text, &boundaries, kept, self.style.overflow);
+                let layout = match best {
+                    Some(layout) => layout,
+                    None => self.shape(&ellipsized, size.width, font_context, palette),
+                };
+                self.layout = layout;
+                self.ellipsized = Some(ellipsized);
+            }
+        }
+    }
+    fn shape(
+        &self,
+        text: &str,
+        width: f64,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Layout<Brush> {
+        let mut layout = build_styled_layout(
+            font_context,
+            palette,
+            text,
+            &self.style,
+            Brush::Solid(self.style.color.unwrap_or(palette.foreground)),
+            &[],
+        );
+        layout.break_all_lines(Some(width as f32));
+        layout.align(Some(width as f32), self.style.align.into());
+        layout
+    }
+    fn fits(&self, layout: &Layout<Brush>, width: f64) -> bool {
+        layout.len() <= self.style.max_lines.unwrap_or(usize::MAX) && layout.width() as f64 <= width
+    }
+    /// The text as it's laid out, shortened if it overflows with an ellipsis
+    fn shown_text(&self) -> &str {
+        self.ellipsized.as_deref().unwrap_or(&self.text)
+    }
+    fn hit_test(&self, position: Point) -> usize {
+        if self.shown_text().is_empty() {
+            return 0;
+        }
+        Cursor::from_point(&self.layout, position.x as f32, position.y as f32).insert_point
+    }
+    /// Byte range of the visual line `idx` is on
+    fn line_range_at(&self, idx: usize) -> Range<usize> {
+        if self.shown_text().is_empty() {
+            return 0..0;
+        }
+        let line_index = Cursor::from_position(&self.layout, idx, true)
+            .path
+            .line_index;
+        match self.layout.get(line_index) {
+            Some(line) => line.text_range(),
+            None => 0..self.shown_text().len(),
+        }
     }
     pub fn text(&self) -> String {
         self.text.clone()
@@ -52,6 +262,14 @@ impl Text {
     pub fn set_text(&mut self, text: String) {
         self.text = text
     }
+    /// Lets the user select the text with the mouse and copy it with Ctrl+C
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+        if !selectable {
+            self.selection = Selection::default();
+            self.is_dragging = false;
+        }
+    }
 }
 
 impl WidgetMarker for Text {}
@@ -59,59 +277,49 @@ impl<State: 'static> Widget<State> for Text {
     fn debug_name(&self) -> &str {
         "text"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
-        for line in self.layout.lines() {
-            for item in line.items() {
-                match item {
-                    parley::layout::PositionedLayoutItem::GlyphRun(glyph_run) => {
-                        let mut x = glyph_run.offset();
-                        let y = glyph_run.baseline();
-                        let run = glyph_run.run();
-                        let font = run.font();
-                        let font_size = run.font_size();
-                        let style = glyph_run.style();
-                        let synthesis = run.synthesis();
-                        let glyph_xform = synthesis
-                            .skew()
-                            .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
-                        let coords = run
-                            .normalized_coords()
-                            .iter()
-                            .map(|coord| {
-                                vello::skrifa::instance::NormalizedCoord::from_bits(*coord)
-                            })
-                            .collect::<Vec<_>>();
-                        scene
-                            .draw_glyphs(font)
-                            .brush(&style.brush)
-                            .glyph_transform(glyph_xform)
-                            .font_size(font_size)
-                            .normalized_coords(&coords)
-                            .draw(
-                                vello::peniko::Fill::NonZero,
-                                glyph_run.glyphs().map(|glyph| {
-                                    let gx = x + glyph.x;
-                                    let gy = y - glyph.y;
-                                    x += glyph.advance;
-                                    vello::Glyph {
-                                        id: glyph.id as _,
-                                        x: gx,
-                                        y: gy,
-                                    }
-                                }),
-                            );
-                    }
-                    parley::layout::PositionedLayoutItem::InlineBox(inline_box) => {
-                        tracing::debug!("Parley inline box: {:?}", inline_box)
-                    }
-                }
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        if let Some(clip) = self.clip {
+            scene.push_layer(
+                BlendMode::default(),
+                1.0,
+                Affine::default(),
+                &Rect::from_origin_size(Point::ZERO, clip),
+            );
+        }
+        if !self.selection.is_collapsed() {
+            for rect in range_rects(&self.layout, self.selection.range()) {
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::default(),
+                    palette.accent.multiply_alpha(0.3),
+                    None,
+                    &rect,
+                );
             }
         }
+        paint_layout(scene, &self.layout, Affine::default());
+        if self.clip.is_some() {
+            scene.pop_layer();
+        }
     }
 
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
-        self.layout_text(self.text.clone(), suggested_size, font_context);
-        Size::new(self.layout.width() as f64, self.layout.height() as f64)
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
+        self.layout_text(self.text.clone(), suggested_size, font_context, palette);
+        if let Some(clip) = self.clip {
+            return clip;
+        }
+        // lines are only placed differently when there's room to move them in
+        let width = if self.style.align != TextAlign::Start && suggested_size.width.is_finite() {
+            suggested_size.width
+        } else {
+            self.layout.width() as f64
+        };
+        Size::new(width, self.layout.height() as f64)
     }
 
     fn children(&self) -> Vec<&super::WidgetData<State>> {
@@ -124,14 +332,341 @@ impl<State: 'static> Widget<State> for Text {
 
     fn event(
         &mut self,
-        _event_context: &mut event::EventContext,
-        _event: event::WidgetEvent,
+        event_context: &mut event::EventContext,
+        event: event::WidgetEvent,
         _state: &mut State,
     ) -> event::Status {
+        if !self.selectable {
+            return event::Status::Ignored;
+        }
+        match event {
+            WidgetEvent::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Character(character),
+                modifiers,
+                ..
+            }) if modifiers.control() => match character.as_str() {
+                "c" => {
+                    if !self.selection.is_collapsed() {
+                        let selected = self.shown_text()[self.selection.range()].to_owned();
+                        event_context.clipboard().write_text(selected);
+                    }
+                    event::Status::Captured
+                }
+                "a" => {
+                    self.selection = Selection {
+                        anchor: 0,
+                        focus: self.shown_text().len(),
+                    };
+                    event_context.repaint_needed = true;
+                    event::Status::Captured
+                }
+                _ => event::Status::Ignored,
+            },
+            WidgetEvent::Mouse(mouse::Event::Press {
+                position,
+                button: MouseButton::Left,
+            }) => {
+                event_context.request_focus();
+                let now = Instant::now();
+                let count = match self.last_click {
+                    Some((time, last_position, count))
+                        if now - time <= DOUBLE_CLICK_TIME
+                            && (position - last_position).hypot() <= DOUBLE_CLICK_DISTANCE =>
+                    {
+                        count % 3 + 1
+                    }
+                    _ => 1,
+                };
+                self.last_click = Some((now, position, count));
+                let idx = self.hit_test(position);
+                let range = match count {
+                    1 => idx..idx,
+                    2 => word_range_at(self.shown_text(), idx),
+                    _ => self.line_range_at(idx),
+                };
+                self.selection = Selection {
+                    anchor: range.start,
+                    focus: range.end,
+                };
+                self.is_dragging = count == 1;
+                event_context.repaint_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Move { position }) => {
+                event_context.set_cursor(CursorIcon::Text);
+                if self.is_dragging {
+                    self.selection.focus = self.hit_test(position);
+                    event_context.repaint_needed = true;
+                }
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Release {
+                button: MouseButton::Left,
+                ..
+            }) => {
+                self.is_dragging = false;
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn set_hover(&mut self, hover: bool) -> event::Status {
+        if !hover {
+            self.is_dragging = false;
+        }
         event::Status::Ignored
     }
 
-    fn set_hover(&mut self, _hover: bool) -> event::Status {
+    fn accepts_focus(&self) -> bool {
+        self.selectable
+    }
+
+    fn set_focus(&mut self, focus: bool) -> event::Status {
+        // like other labels the selection goes away once something else is focused
+        if !focus && !self.selection.is_collapsed() {
+            self.selection = Selection::default();
+            return event::Status::Captured;
+        }
         event::Status::Ignored
     }
 }
+
+/// Shapes `text` with the font stack and size of the palette, lines still need to be broken
+pub(crate) fn build_layout(
+    font_context: &mut FontContext,
+    palette: &Palette,
+    text: &str,
+    brush: Brush,
+) -> Layout<Brush> {
+    build_styled_layout(
+        font_context,
+        palette,
+        text,
+        &TextStyle::default(),
+        brush,
+        &[],
+    )
+}
+
+/// Like [`build_layout`], with the fields `style` sets taking over from the palette
+/// and `ranged` properties applied on top for their byte range of `text`
+pub(crate) fn build_styled_layout(
+    font_context: &mut FontContext,
+    palette: &Palette,
+    text: &str,
+    style: &TextStyle,
+    brush: Brush,
+    ranged: &[(StyleProperty<'static, Brush>, Range<usize>)],
+) -> Layout<Brush> {
+    let mut lcx = parley::LayoutContext::new();
+    let mut layout_builder = lcx.ranged_builder(font_context, text, 1.0);
+    let font_families = style
+        .font_families
+        .as_ref()
+        .unwrap_or(&palette.font_families)
+        .iter()
+        .map(|family| FontFamily::Named(Cow::Borrowed(family.as_str())))
+        .collect::<Vec<_>>();
+    layout_builder.push_default(StyleProperty::FontStack(parley::style::FontStack::List(
+        Cow::Owned(font_families),
+    )));
+    layout_builder.push_default(StyleProperty::FontSize(
+        style.font_size.unwrap_or(palette.font_size),
+    ));
+    layout_builder.push_default(StyleProperty::Brush(brush));
+    layout_builder.push_default(StyleProperty::FontWeight(style.weight));
+    if style.italic {
+        layout_builder.push_default(StyleProperty::FontStyle(parley::style::FontStyle::Italic));
+    }
+    layout_builder.push_default(StyleProperty::LetterSpacing(style.letter_spacing));
+    layout_builder.push_default(StyleProperty::LineHeight(style.line_height));
+    for (property, range) in ranged {
+        layout_builder.push(property.clone(), range.clone());
+    }
+    layout_builder.build(text)
+}
+
+/// Rectangles covering the byte `range` of `layout`, one for every line it is on
+pub(crate) fn range_rects(layout: &Layout<Brush>, range: Range<usize>) -> Vec<Rect> {
+    let mut rects = vec![];
+    for line in layout.lines() {
+        let line_range = line.text_range();
+        if line_range.end <= range.start || line_range.start >= range.end {
+            continue;
+        }
+        let metrics = line.metrics();
+        let x0 = if line_range.contains(&range.start) {
+            Cursor::from_position(layout, range.start, true).offset
+        } else {
+            metrics.offset
+        };
+        let x1 = if line_range.contains(&range.end) {
+            Cursor::from_position(layout, range.end, true).offset
+        } else {
+            metrics.offset + metrics.advance
+        };
+        rects.push(Rect::new(
+            x0 as f64,
+            (metrics.baseline - metrics.ascent - metrics.leading * 0.5) as f64,
+            x1 as f64,
+            (metrics.baseline + metrics.descent + metrics.leading * 0.5) as f64,
+        ));
+    }
+    rects
+}
+
+/// Draws every glyph run of `layout` with the brush of its style
+pub(crate) fn paint_layout(scene: &mut vello::Scene, layout: &Layout<Brush>, transform: Affine) {
+    for line in layout.lines() {
+        for item in line.items() {
+            match item {
+                parley::layout::PositionedLayoutItem::GlyphRun(glyph_run) => {
+                    let mut x = glyph_run.offset();
+                    let y = glyph_run.baseline();
+                    let run = glyph_run.run();
+                    let font = run.font();
+                    let font_size = run.font_size();
+                    let style = glyph_run.style();
+                    let synthesis = run.synthesis();
+                    let glyph_xform = synthesis
+                        .skew()
+                        .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
+                    let coords = run
+                        .normalized_coords()
+                        .iter()
+                        .map(|coord| vello::skrifa::instance::NormalizedCoord::from_bits(*coord))
+                        .collect::<Vec<_>>();
+                    scene
+                        .draw_glyphs(font)
+                        .brush(&style.brush)
+                        .transform(transform)
+                        .glyph_transform(glyph_xform)
+                        .font_size(font_size)
+                        .normalized_coords(&coords)
+                        .draw(
+                            vello::peniko::Fill::NonZero,
+                            glyph_run.glyphs().map(|glyph| {
+                                let gx = x + glyph.x;
+                                let gy = y - glyph.y;
+                                x += glyph.advance;
+                                vello::Glyph {
+                                    id: glyph.id as _,
+                                    x: gx,
+                                    y: gy,
+                                }
+                            }),
+                        );
+                    let metrics = run.metrics();
+                    if let Some(underline) = &style.underline {
+                        paint_decoration(
+                            scene,
+                            &glyph_run,
+                            underline,
+                            metrics.underline_offset,
+                            metrics.underline_size,
+                            transform,
+                        );
+                    }
+                    if let Some(strikethrough) = &style.strikethrough {
+                        paint_decoration(
+                            scene,
+                            &glyph_run,
+                            strikethrough,
+                            metrics.strikethrough_offset,
+                            metrics.strikethrough_size,
+                            transform,
+                        );
+                    }
+                }
+                parley::layout::PositionedLayoutItem::InlineBox(inline_box) => {
+                    tracing::debug!("Parley inline box: {:?}", inline_box)
+                }
+            }
+        }
+    }
+}
+
+/// Byte index of every character of `text`, followed by the length of the text
+fn char_boundaries(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(idx, _)| idx)
+        .chain([text.len()])
+        .collect()
+}
+
+/// `text` with only `kept` of its characters left, `boundaries` are the byte indices
+/// from [`char_boundaries`]
+fn ellipsized(text: &str, boundaries: &[usize], kept: usize, overflow: TextOverflow) -> String {
+    let char_count = boundaries.len() - 1;
+    match overflow {
+        TextOverflow::MiddleEllipsis => {
+            let tail = kept / 2;
+            let head = kept - tail;
+            format!(
+                "{}…{}",
+                text[..boundaries[head]].trim_end(),
+                text[boundaries[char_count - tail]..].trim_start()
+            )
+        }
+        _ => format!("{}…", text[..boundaries[kept]].trim_end()),
+    }
+}
+
+/// Draws an underline or strikethrough across `glyph_run`, the font's metrics are used
+/// unless the style sets its own
+fn paint_decoration(
+    scene: &mut vello::Scene,
+    glyph_run: &GlyphRun<Brush>,
+    decoration: &Decoration<Brush>,
+    default_offset: f32,
+    default_size: f32,
+    transform: Affine,
+) {
+    let offset = decoration.offset.unwrap_or(default_offset) as f64;
+    let size = decoration.size.unwrap_or(default_size) as f64;
+    let x = glyph_run.offset() as f64;
+    let y = glyph_run.baseline() as f64 - offset;
+    scene.fill(
+        Fill::NonZero,
+        transform,
+        &decoration.brush,
+        None,
+        &Rect::new(x, y, x + glyph_run.advance() as f64, y + size),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char_boundaries_of_multi_byte_text() {
+        assert_eq!(char_boundaries("aé日"), [0, 1, 3, 6]);
+        assert_eq!(char_boundaries(""), [0]);
+    }
+
+    #[test]
+    fn ellipsis_keeps_whole_characters() {
+        let text = "Grüße aus Köln";
+        let boundaries = char_boundaries(text);
+        let ellipsis = |kept| ellipsized(text, &boundaries, kept, TextOverflow::Ellipsis);
+        assert_eq!(ellipsis(0), "…");
+        assert_eq!(ellipsis(3), "Grü…");
+        assert_eq!(ellipsis(4), "Grüß…");
+        // trailing whitespace is dropped before the ellipsis
+        assert_eq!(ellipsis(6), "Grüße…");
+        assert_eq!(ellipsis(12), "Grüße aus Kö…");
+    }
+
+    #[test]
+    fn middle_ellipsis_keeps_both_ends() {
+        let text = "日本語のファイル.txt";
+        let boundaries = char_boundaries(text);
+        let ellipsis = |kept| ellipsized(text, &boundaries, kept, TextOverflow::MiddleEllipsis);
+        assert_eq!(ellipsis(0), "…");
+        assert_eq!(ellipsis(1), "日…");
+        assert_eq!(ellipsis(6), "日本語…txt");
+        assert_eq!(ellipsis(7), "日本語の…txt");
+    }
+}
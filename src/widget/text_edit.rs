@@ -0,0 +1,510 @@
+use core::ops::Range;
+
+use crate::event::keyboard::{Key, Modifiers};
+use crate::Clipboard;
+
+/// A selection inside an editable text, `anchor` is where the selection started
+/// and `focus` is where the caret is drawn. Both are byte indices into the text
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct Selection {
+    pub anchor: usize,
+    pub focus: usize,
+}
+
+impl Selection {
+    pub fn caret(idx: usize) -> Self {
+        Self {
+            anchor: idx,
+            focus: idx,
+        }
+    }
+    pub fn is_collapsed(&self) -> bool {
+        self.anchor == self.focus
+    }
+    pub fn range(&self) -> Range<usize> {
+        self.anchor.min(self.focus)..self.anchor.max(self.focus)
+    }
+    /// Moves the caret to `idx`, keeping the anchor in place if `extend` is set
+    pub fn move_to(&mut self, idx: usize, extend: bool) {
+        self.focus = idx;
+        if !extend {
+            self.anchor = idx;
+        }
+    }
+    /// Makes sure both ends of the selection are valid indices into `text`
+    pub fn clamp(&mut self, text: &str) {
+        let clamp = |idx: usize| {
+            let mut idx = idx.min(text.len());
+            while !text.is_char_boundary(idx) {
+                idx -= 1;
+            }
+            idx
+        };
+        self.anchor = clamp(self.anchor);
+        self.focus = clamp(self.focus);
+    }
+}
+
+/// Text an input method is composing, shown at `position` (a byte index into
+/// the edited text) until it is committed. Indices into the text with the preedit
+/// spliced in are called display indices
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Preedit {
+    pub text: String,
+    pub cursor: Option<(usize, usize)>,
+    pub position: usize,
+}
+
+impl Preedit {
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = None;
+    }
+    pub fn display_text(&self, text: &str) -> String {
+        let mut display_text = text.to_owned();
+        display_text.insert_str(self.position, &self.text);
+        display_text
+    }
+    pub fn to_display(&self, idx: usize) -> usize {
+        if idx > self.position {
+            idx + self.text.len()
+        } else {
+            idx
+        }
+    }
+    pub fn from_display(&self, idx: usize) -> usize {
+        if idx <= self.position {
+            idx
+        } else if idx < self.position + self.text.len() {
+            self.position
+        } else {
+            idx - self.text.len()
+        }
+    }
+    /// Display range covered by the preedit text, to be underlined
+    pub fn range(&self) -> Range<usize> {
+        self.position..self.position + self.text.len()
+    }
+    /// Display index of the caret, `None` if it should be hidden
+    pub fn caret(&self, focus: usize) -> Option<usize> {
+        if self.is_empty() {
+            Some(focus)
+        } else {
+            self.cursor.map(|(_, end)| self.position + end)
+        }
+    }
+}
+
+/// Replaces the selected text with `insert` and collapses the selection after it
+pub(crate) fn replace_selection(text: &mut String, selection: &mut Selection, insert: &str) {
+    let range = selection.range();
+    text.replace_range(range.clone(), insert);
+    *selection = Selection::caret(range.start + insert.len());
+}
+
+/// Handles Ctrl+C, Ctrl+X and Ctrl+V: copy and cut put the selected text on the
+/// clipboard, cut and paste return what the selection gets replaced with. `None` for
+/// other keys, when there's nothing selected to cut or no text to paste.
+/// Single line fields get pasted line breaks and tabs as spaces
+pub(crate) fn clipboard_shortcut(
+    key: &Key,
+    modifiers: Modifiers,
+    text: &str,
+    selection: &Selection,
+    clipboard: &Clipboard,
+    single_line: bool,
+) -> Option<String> {
+    let Key::Character(character) = key else {
+        return None;
+    };
+    if !modifiers.control() {
+        return None;
+    }
+    let selected = &text[selection.range()];
+    match character.as_str() {
+        "c" => {
+            if !selected.is_empty() {
+                clipboard.write_text(selected);
+            }
+            None
+        }
+        "x" if !selected.is_empty() => {
+            clipboard.write_text(selected);
+            Some(String::new())
+        }
+        "v" => {
+            let pasted = clipboard
+                .read_text()?
+                .replace("\r\n", "\n")
+                .chars()
+                .map(|c| match c {
+                    '\n' | '\t' if single_line => ' ',
+                    c => c,
+                })
+                .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+                .collect::<String>();
+            (!pasted.is_empty()).then_some(pasted)
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn prev_char_boundary(text: &str, idx: usize) -> usize {
+    text[..idx]
+        .char_indices()
+        .next_back()
+        .map(|(idx, _)| idx)
+        .unwrap_or(0)
+}
+
+pub(crate) fn next_char_boundary(text: &str, idx: usize) -> usize {
+    text[idx..]
+        .chars()
+        .next()
+        .map(|c| idx + c.len_utf8())
+        .unwrap_or(text.len())
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Start of the word before `idx`, skipping any separators in between
+pub(crate) fn prev_word_boundary(text: &str, idx: usize) -> usize {
+    let mut chars = text[..idx].char_indices().rev().peekable();
+    while chars.next_if(|(_, c)| !is_word_char(*c)).is_some() {}
+    let mut boundary = 0;
+    while let Some((idx, _)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+        boundary = idx;
+    }
+    boundary
+}
+
+/// End of the word after `idx`, skipping any separators in between
+pub(crate) fn next_word_boundary(text: &str, idx: usize) -> usize {
+    let mut chars = text[idx..].char_indices().peekable();
+    while chars.next_if(|(_, c)| !is_word_char(*c)).is_some() {}
+    while chars.next_if(|(_, c)| is_word_char(*c)).is_some() {}
+    chars.peek().map(|(i, _)| idx + i).unwrap_or(text.len())
+}
+
+/// The word around `idx`, or the run of separators around it if it isn't in a word
+pub(crate) fn word_range_at(text: &str, idx: usize) -> Range<usize> {
+    let in_word = text[idx..].chars().next().is_some_and(is_word_char);
+    let same_kind = |c: char| is_word_char(c) == in_word;
+    let start = text[..idx]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| same_kind(*c))
+        .last()
+        .map(|(idx, _)| idx)
+        .unwrap_or(idx);
+    let end = text[idx..]
+        .char_indices()
+        .find(|(_, c)| !same_kind(*c))
+        .map(|(i, _)| idx + i)
+        .unwrap_or(text.len());
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryClipboard;
+
+    // bytes: "héllo" 0..6 with "é" 1..3, "wörld" 7..13, "👋" 14..18, "foo_bar" 19..26
+    const TEXT: &str = "héllo wörld 👋 foo_bar";
+
+    fn ctrl(character: &str) -> Key {
+        Key::Character(character.to_owned())
+    }
+
+    fn clipboard_with(text: Option<&str>) -> Clipboard {
+        let clipboard = Clipboard::new(MemoryClipboard::default());
+        if let Some(text) = text {
+            clipboard.write_text(text);
+        }
+        clipboard
+    }
+
+    fn paste(pasted: &str, single_line: bool) -> Option<String> {
+        clipboard_shortcut(
+            &ctrl("v"),
+            Modifiers::CTRL,
+            "",
+            &Selection::caret(0),
+            &clipboard_with(Some(pasted)),
+            single_line,
+        )
+    }
+
+    #[test]
+    fn char_boundaries_step_over_multi_byte_chars() {
+        assert_eq!(next_char_boundary(TEXT, 1), 3);
+        assert_eq!(prev_char_boundary(TEXT, 3), 1);
+        assert_eq!(next_char_boundary(TEXT, 14), 18);
+        assert_eq!(prev_char_boundary(TEXT, 18), 14);
+        assert_eq!(prev_char_boundary(TEXT, 0), 0);
+        assert_eq!(next_char_boundary(TEXT, TEXT.len()), TEXT.len());
+    }
+
+    #[test]
+    fn next_word_boundary_skips_separators() {
+        assert_eq!(next_word_boundary(TEXT, 0), 6);
+        assert_eq!(next_word_boundary(TEXT, 3), 6);
+        assert_eq!(next_word_boundary(TEXT, 6), 13);
+        // the emoji isn't a word character
+        assert_eq!(next_word_boundary(TEXT, 13), TEXT.len());
+        assert_eq!(next_word_boundary(TEXT, TEXT.len()), TEXT.len());
+    }
+
+    #[test]
+    fn prev_word_boundary_skips_separators() {
+        assert_eq!(prev_word_boundary(TEXT, TEXT.len()), 19);
+        assert_eq!(prev_word_boundary(TEXT, 19), 7);
+        assert_eq!(prev_word_boundary(TEXT, 10), 7);
+        assert_eq!(prev_word_boundary(TEXT, 7), 0);
+        assert_eq!(prev_word_boundary(TEXT, 3), 0);
+        assert_eq!(prev_word_boundary(TEXT, 0), 0);
+    }
+
+    #[test]
+    fn word_range_at_words_and_separators() {
+        assert_eq!(word_range_at(TEXT, 0), 0..6);
+        assert_eq!(word_range_at(TEXT, 3), 0..6);
+        assert_eq!(word_range_at(TEXT, 8), 7..13);
+        assert_eq!(word_range_at(TEXT, 14), 13..19);
+        assert_eq!(word_range_at(TEXT, 20), 19..TEXT.len());
+        assert_eq!(word_range_at(TEXT, TEXT.len()), TEXT.len()..TEXT.len());
+        assert_eq!(word_range_at("", 0), 0..0);
+    }
+
+    #[test]
+    fn preedit_in_the_middle_of_the_text() {
+        // "a|b" composing "xyz" shows "axyzb"
+        let preedit = Preedit {
+            text: "xyz".to_owned(),
+            cursor: Some((1, 2)),
+            position: 1,
+        };
+        assert_eq!(preedit.display_text("ab"), "axyzb");
+        assert_eq!(preedit.range(), 1..4);
+        assert_eq!(preedit.to_display(0), 0);
+        assert_eq!(preedit.to_display(1), 1);
+        assert_eq!(preedit.to_display(2), 5);
+        assert_eq!(preedit.from_display(0), 0);
+        assert_eq!(preedit.from_display(1), 1);
+        assert_eq!(preedit.from_display(4), 1);
+        assert_eq!(preedit.from_display(5), 2);
+        assert_eq!(preedit.caret(0), Some(3));
+    }
+
+    #[test]
+    fn preedit_from_display_inside_the_preedit() {
+        let preedit = Preedit {
+            text: "xyz".to_owned(),
+            cursor: None,
+            position: 1,
+        };
+        assert_eq!(preedit.from_display(2), 1);
+        assert_eq!(preedit.from_display(3), 1);
+        assert_eq!(preedit.caret(0), None);
+    }
+
+    #[test]
+    fn preedit_with_multi_byte_text() {
+        // "é|!" composing "日本" shows "é日本!"
+        let preedit = Preedit {
+            text: "日本".to_owned(),
+            cursor: Some((6, 6)),
+            position: 2,
+        };
+        assert_eq!(preedit.display_text("é!"), "é日本!");
+        assert_eq!(preedit.to_display(2), 2);
+        assert_eq!(preedit.to_display(3), 9);
+        assert_eq!(preedit.from_display(5), 2);
+        assert_eq!(preedit.from_display(9), 3);
+        assert_eq!(preedit.caret(0), Some(8));
+    }
+
+    #[test]
+    fn preedit_at_the_edges() {
+        let start = Preedit {
+            text: "👋".to_owned(),
+            cursor: None,
+            position: 0,
+        };
+        assert_eq!(start.display_text("é"), "👋é");
+        assert_eq!(start.to_display(0), 0);
+        assert_eq!(start.to_display(2), 6);
+        assert_eq!(start.from_display(6), 2);
+
+        let end = Preedit {
+            position: 2,
+            ..start
+        };
+        assert_eq!(end.display_text("é"), "é👋");
+        assert_eq!(end.to_display(2), 2);
+        assert_eq!(end.from_display(4), 2);
+        assert_eq!(end.from_display(6), 2);
+
+        let empty = Preedit::default();
+        assert_eq!(empty.to_display(3), 3);
+        assert_eq!(empty.from_display(3), 3);
+        assert_eq!(empty.caret(3), Some(3));
+    }
+
+    #[test]
+    fn replace_selection_at_the_edges() {
+        let mut text = "é".to_owned();
+        let mut selection = Selection::caret(0);
+        replace_selection(&mut text, &mut selection, "👋");
+        assert_eq!(text, "👋é");
+        assert_eq!(selection, Selection::caret(4));
+
+        let mut selection = Selection::caret(text.len());
+        replace_selection(&mut text, &mut selection, "!");
+        assert_eq!(text, "👋é!");
+        assert_eq!(selection, Selection::caret(7));
+
+        let mut selection = Selection {
+            anchor: 6,
+            focus: 0,
+        };
+        replace_selection(&mut text, &mut selection, "");
+        assert_eq!(text, "!");
+        assert_eq!(selection, Selection::caret(0));
+    }
+
+    #[test]
+    fn selection_clamp_lands_on_char_boundaries() {
+        let mut selection = Selection {
+            anchor: 1,
+            focus: 10,
+        };
+        selection.clamp("é");
+        assert_eq!(
+            selection,
+            Selection {
+                anchor: 0,
+                focus: 2
+            }
+        );
+    }
+
+    #[test]
+    fn clipboard_shortcut_copies_the_selection() {
+        let clipboard = clipboard_with(None);
+        let selection = Selection {
+            anchor: 13,
+            focus: 7,
+        };
+        let result = clipboard_shortcut(
+            &ctrl("c"),
+            Modifiers::CTRL,
+            TEXT,
+            &selection,
+            &clipboard,
+            false,
+        );
+        assert_eq!(result, None);
+        assert_eq!(clipboard.read_text().as_deref(), Some("wörld"));
+    }
+
+    #[test]
+    fn clipboard_shortcut_copy_without_selection_keeps_the_clipboard() {
+        let clipboard = clipboard_with(Some("kept"));
+        let result = clipboard_shortcut(
+            &ctrl("c"),
+            Modifiers::CTRL,
+            TEXT,
+            &Selection::caret(3),
+            &clipboard,
+            false,
+        );
+        assert_eq!(result, None);
+        assert_eq!(clipboard.read_text().as_deref(), Some("kept"));
+    }
+
+    #[test]
+    fn clipboard_shortcut_cuts_the_selection() {
+        let clipboard = clipboard_with(None);
+        let selection = Selection {
+            anchor: 14,
+            focus: 18,
+        };
+        let result = clipboard_shortcut(
+            &ctrl("x"),
+            Modifiers::CTRL,
+            TEXT,
+            &selection,
+            &clipboard,
+            true,
+        );
+        assert_eq!(result.as_deref(), Some(""));
+        assert_eq!(clipboard.read_text().as_deref(), Some("👋"));
+
+        let nothing_selected = clipboard_shortcut(
+            &ctrl("x"),
+            Modifiers::CTRL,
+            TEXT,
+            &Selection::caret(0),
+            &clipboard,
+            true,
+        );
+        assert_eq!(nothing_selected, None);
+    }
+
+    #[test]
+    fn clipboard_shortcut_ignores_other_keys() {
+        let clipboard = clipboard_with(Some("text"));
+        let selection = Selection {
+            anchor: 0,
+            focus: 6,
+        };
+        let shortcut = |key: &Key, modifiers| {
+            clipboard_shortcut(key, modifiers, TEXT, &selection, &clipboard, false)
+        };
+        assert_eq!(shortcut(&ctrl("v"), Modifiers::empty()), None);
+        assert_eq!(shortcut(&ctrl("x"), Modifiers::SHIFT), None);
+        assert_eq!(shortcut(&ctrl("a"), Modifiers::CTRL), None);
+        assert_eq!(shortcut(&Key::Unidentified, Modifiers::CTRL), None);
+        assert_eq!(clipboard.read_text().as_deref(), Some("text"));
+    }
+
+    #[test]
+    fn paste_keeps_multi_byte_text() {
+        assert_eq!(paste("é👋", true).as_deref(), Some("é👋"));
+    }
+
+    #[test]
+    fn paste_normalizes_line_breaks_and_control_characters() {
+        assert_eq!(
+            paste("a\r\nb\tc\u{7}d\u{1b}", false).as_deref(),
+            Some("a\nb\tcd")
+        );
+        assert_eq!(paste("a\r\nb\nc", false).as_deref(), Some("a\nb\nc"));
+    }
+
+    #[test]
+    fn paste_into_single_line_turns_line_breaks_and_tabs_into_spaces() {
+        assert_eq!(paste("a\r\nb\tc\u{7}d\n", true).as_deref(), Some("a b cd "));
+    }
+
+    #[test]
+    fn paste_without_text_returns_none() {
+        assert_eq!(paste("\u{7}\u{1b}", false), None);
+        let empty = clipboard_shortcut(
+            &ctrl("v"),
+            Modifiers::CTRL,
+            "",
+            &Selection::caret(0),
+            &clipboard_with(None),
+            false,
+        );
+        assert_eq!(empty, None);
+    }
+}
@@ -0,0 +1,628 @@
+use crate::event::ime;
+use crate::event::keyboard::{self, Key, NamedKey};
+use crate::event::{self, mouse, mouse::MouseButton, WidgetEvent};
+use crate::widget::text::{build_layout, paint_layout};
+use crate::widget::text_edit::{
+    clipboard_shortcut, next_char_boundary, next_word_boundary, prev_char_boundary,
+    prev_word_boundary, replace_selection, Preedit, Selection,
+};
+use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::Palette;
+use crate::Task;
+use core::ops::Range;
+use parley::layout::Cursor;
+use parley::{FontContext, Layout};
+use std::sync::Arc;
+use vello::peniko::kurbo::{Affine, Point, Rect, Size, Stroke};
+use vello::peniko::{BlendMode, Brush, Fill};
+use winit::window::CursorIcon;
+
+const DEFAULT_SIZE: Size = Size::new(300., 200.);
+const PADDING: f64 = 9.;
+const RADIUS: f64 = 6.;
+const CARET_WIDTH: f64 = 1.;
+const MAX_UNDO_STEPS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+struct UndoStep {
+    text: String,
+    selection: Selection,
+}
+
+pub struct TextEditor<State> {
+    pub(crate) width: Option<f64>,
+    pub(crate) height: Option<f64>,
+    pub(crate) on_change: Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>,
+    size: Size,
+    text: String,
+    layout: Layout<Brush>,
+    selection: Selection,
+    preedit: Preedit,
+    /// Horizontal position vertical caret movement tries to keep
+    goal_x: Option<f32>,
+    scroll_y: f64,
+    /// Set when the caret moves or the text is edited, the next paint scrolls the caret
+    /// into view once so wheel scrolling isn't undone every frame
+    scroll_to_caret: bool,
+    undo_stack: Vec<UndoStep>,
+    redo_stack: Vec<UndoStep>,
+    last_edit: EditKind,
+    is_dragging: bool,
+    hovered: bool,
+    focused: bool,
+}
+
+impl<State> TextEditor<State> {
+    pub fn new(
+        text: String,
+        width: Option<f64>,
+        height: Option<f64>,
+        on_change: Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            on_change,
+            size: Size::ZERO,
+            text,
+            layout: Layout::new(),
+            selection: Selection::caret(0),
+            preedit: Preedit::default(),
+            goal_x: None,
+            scroll_y: 0.,
+            scroll_to_caret: false,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            last_edit: EditKind::Other,
+            is_dragging: false,
+            hovered: false,
+            focused: false,
+        }
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Replaces the text from the outside, this clears the undo history
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+        self.selection.clamp(&self.text);
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = EditKind::Other;
+    }
+
+    fn text_origin(&self) -> Point {
+        Point::new(PADDING, PADDING - self.scroll_y)
+    }
+
+    /// Whether there is neither text nor preedit text to show
+    fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.preedit.is_empty()
+    }
+
+    /// Offset along the line and index of the line of the caret drawn before
+    /// the byte at display index `idx`
+    fn caret_position(&self, idx: usize) -> (f32, usize) {
+        if self.is_empty() {
+            return (0., 0);
+        }
+        let cursor = Cursor::from_position(&self.layout, idx, true);
+        (cursor.offset, cursor.path.line_index)
+    }
+
+    /// Top and bottom of the line at `line_index`
+    fn line_extent(&self, line_index: usize) -> (f64, f64) {
+        match self.layout.get(line_index) {
+            Some(line) => {
+                let metrics = line.metrics();
+                (
+                    (metrics.baseline - metrics.ascent - metrics.leading * 0.5) as f64,
+                    (metrics.baseline + metrics.descent + metrics.leading * 0.5) as f64,
+                )
+            }
+            None => (0., self.layout.height() as f64),
+        }
+    }
+
+    /// Horizontal extent and line index of every line segment covered by the display `range`
+    fn range_segments(&self, range: Range<usize>) -> Vec<(f64, f64, usize)> {
+        let mut segments = vec![];
+        for (line_index, line) in self.layout.lines().enumerate() {
+            let line_range = line.text_range();
+            if line_range.end <= range.start || line_range.start >= range.end {
+                continue;
+            }
+            let metrics = line.metrics();
+            let x0 = if line_range.contains(&range.start) {
+                self.caret_position(range.start).0
+            } else {
+                metrics.offset
+            };
+            let x1 = if line_range.contains(&range.end) {
+                self.caret_position(range.end).0
+            } else {
+                metrics.offset + metrics.advance
+            };
+            segments.push((x0 as f64, x1 as f64, line_index));
+        }
+        segments
+    }
+
+    fn hit_test(&self, position: Point) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let origin = self.text_origin();
+        let idx = Cursor::from_point(
+            &self.layout,
+            (position.x - origin.x) as f32,
+            (position.y - origin.y) as f32,
+        )
+        .insert_point;
+        self.preedit.from_display(idx)
+    }
+
+    /// Index on the line `line_delta` lines away from the caret, keeping its horizontal position
+    fn vertical_move(&mut self, line_delta: isize) -> usize {
+        let (offset, line_index) =
+            self.caret_position(self.preedit.to_display(self.selection.focus));
+        let goal_x = *self.goal_x.get_or_insert(offset);
+        let target_line = line_index as isize + line_delta;
+        if target_line < 0 {
+            return 0;
+        }
+        match self.layout.get(target_line as usize) {
+            Some(line) => {
+                let metrics = line.metrics();
+                let idx = Cursor::from_point(&self.layout, goal_x, metrics.baseline).insert_point;
+                self.preedit.from_display(idx)
+            }
+            None => self.text.len(),
+        }
+    }
+
+    fn line_boundary(&self, end: bool) -> usize {
+        let (_, line_index) = self.caret_position(self.preedit.to_display(self.selection.focus));
+        let Some(line) = self.layout.get(line_index) else {
+            return if end { self.text.len() } else { 0 };
+        };
+        let range = line.text_range();
+        if !end {
+            return self.preedit.from_display(range.start);
+        }
+        let line_end = self.preedit.from_display(range.end);
+        // don't jump past the hard line break that ends this line
+        if self.text[..line_end].ends_with('\n') {
+            line_end - 1
+        } else {
+            line_end
+        }
+    }
+
+    fn push_undo(&mut self, kind: EditKind) {
+        // consecutive insertions or deletions are undone together
+        if kind != EditKind::Other && kind == self.last_edit {
+            return;
+        }
+        self.undo_stack.push(UndoStep {
+            text: self.text.clone(),
+            selection: self.selection,
+        });
+        if self.undo_stack.len() > MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+        self.last_edit = kind;
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some(step) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(UndoStep {
+            text: core::mem::replace(&mut self.text, step.text),
+            selection: self.selection,
+        });
+        self.selection = step.selection;
+        self.last_edit = EditKind::Other;
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some(step) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(UndoStep {
+            text: core::mem::replace(&mut self.text, step.text),
+            selection: self.selection,
+        });
+        self.selection = step.selection;
+        self.last_edit = EditKind::Other;
+        true
+    }
+
+    /// Applies a key press to the text, returns true if the text was changed
+    fn handle_key(
+        &mut self,
+        key: &Key,
+        text: Option<&str>,
+        modifiers: keyboard::Modifiers,
+    ) -> bool {
+        let extend = modifiers.shift();
+        let collapsed = self.selection.is_collapsed();
+        if !matches!(
+            key,
+            Key::Named(NamedKey::ArrowUp | NamedKey::ArrowDown | NamedKey::Shift)
+        ) {
+            self.goal_x = None;
+        }
+        let movement = match key {
+            Key::Named(NamedKey::ArrowLeft) => Some(if !collapsed && !extend {
+                self.selection.range().start
+            } else if modifiers.control() {
+                prev_word_boundary(&self.text, self.selection.focus)
+            } else {
+                prev_char_boundary(&self.text, self.selection.focus)
+            }),
+            Key::Named(NamedKey::ArrowRight) => Some(if !collapsed && !extend {
+                self.selection.range().end
+            } else if modifiers.control() {
+                next_word_boundary(&self.text, self.selection.focus)
+            } else {
+                next_char_boundary(&self.text, self.selection.focus)
+            }),
+            Key::Named(NamedKey::ArrowUp) => Some(self.vertical_move(-1)),
+            Key::Named(NamedKey::ArrowDown) => Some(self.vertical_move(1)),
+            Key::Named(NamedKey::Home) if modifiers.control() => Some(0),
+            Key::Named(NamedKey::End) if modifiers.control() => Some(self.text.len()),
+            Key::Named(NamedKey::Home) => Some(self.line_boundary(false)),
+            Key::Named(NamedKey::End) => Some(self.line_boundary(true)),
+            _ => None,
+        };
+        if let Some(idx) = movement {
+            self.selection.move_to(idx, extend);
+            self.last_edit = EditKind::Other;
+            return false;
+        }
+        match key {
+            Key::Named(NamedKey::Backspace) | Key::Named(NamedKey::Delete) => {
+                if collapsed {
+                    let backwards = *key == Key::Named(NamedKey::Backspace);
+                    self.selection.anchor = match (backwards, modifiers.control()) {
+                        (true, true) => prev_word_boundary(&self.text, self.selection.focus),
+                        (true, false) => prev_char_boundary(&self.text, self.selection.focus),
+                        (false, true) => next_word_boundary(&self.text, self.selection.focus),
+                        (false, false) => next_char_boundary(&self.text, self.selection.focus),
+                    };
+                }
+                if self.selection.is_collapsed() {
+                    return false;
+                }
+                self.push_undo(EditKind::Delete);
+                replace_selection(&mut self.text, &mut self.selection, "");
+                true
+            }
+            Key::Named(NamedKey::Enter) => {
+                self.push_undo(EditKind::Other);
+                replace_selection(&mut self.text, &mut self.selection, "\n");
+                true
+            }
+            Key::Character(character) if modifiers.control() => match character.as_str() {
+                "a" => {
+                    self.selection = Selection {
+                        anchor: 0,
+                        focus: self.text.len(),
+                    };
+                    false
+                }
+                "z" if modifiers.shift() => self.redo(),
+                "Z" | "y" => self.redo(),
+                "z" => self.undo(),
+                _ => false,
+            },
+            _ => {
+                if modifiers.control() || modifiers.alt() {
+                    return false;
+                }
+                let insert = text
+                    .unwrap_or_default()
+                    .chars()
+                    .filter(|c| !c.is_control())
+                    .collect::<String>();
+                if insert.is_empty() {
+                    return false;
+                }
+                self.push_undo(if insert.chars().all(char::is_whitespace) {
+                    EditKind::Other
+                } else {
+                    EditKind::Insert
+                });
+                replace_selection(&mut self.text, &mut self.selection, &insert);
+                true
+            }
+        }
+    }
+
+    fn max_scroll(&self) -> f64 {
+        (self.layout.height() as f64 + PADDING * 2. - self.size.height).max(0.)
+    }
+}
+
+impl<State> WidgetMarker for TextEditor<State> {}
+impl<State: 'static> Widget<State> for TextEditor<State> {
+    fn debug_name(&self) -> &str {
+        "text_editor"
+    }
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        let bounds = self.size.to_rect();
+        scene.fill(
+            Fill::NonZero,
+            Affine::default(),
+            palette
+                .foreground
+                .multiply_alpha(if self.hovered { 0.1 } else { 0.06 }),
+            None,
+            &bounds.to_rounded_rect(RADIUS),
+        );
+        if self.focused {
+            scene.stroke(
+                &Stroke::new(2.),
+                Affine::default(),
+                palette.accent.multiply_alpha(0.5),
+                None,
+                &bounds.inset(-1.).to_rounded_rect(RADIUS),
+            );
+        }
+
+        let caret = self.preedit.caret(self.selection.focus);
+        let (caret_x, caret_line) = self.caret_position(caret.unwrap_or(self.preedit.range().end));
+        let (caret_top, caret_bottom) = self.line_extent(caret_line);
+        // bring the caret into the visible part of the editor after it moved
+        if core::mem::take(&mut self.scroll_to_caret) {
+            let visible_height = self.size.height - PADDING * 2.;
+            if caret_bottom - self.scroll_y > visible_height {
+                self.scroll_y = caret_bottom - visible_height;
+            } else if caret_top < self.scroll_y {
+                self.scroll_y = caret_top;
+            }
+            self.scroll_y = self.scroll_y.clamp(0., self.max_scroll());
+        }
+
+        scene.push_layer(
+            BlendMode::default(),
+            1.0,
+            Affine::default(),
+            &bounds.inset(-PADDING + CARET_WIDTH),
+        );
+        let transform = Affine::translate(self.text_origin().to_vec2());
+        if !self.selection.is_collapsed() {
+            let range = self.selection.range();
+            let range = self.preedit.to_display(range.start)..self.preedit.to_display(range.end);
+            for (x0, x1, line_index) in self.range_segments(range) {
+                let (top, bottom) = self.line_extent(line_index);
+                scene.fill(
+                    Fill::NonZero,
+                    transform,
+                    palette.accent.multiply_alpha(0.3),
+                    None,
+                    &Rect::new(x0, top, x1, bottom),
+                );
+            }
+        }
+        paint_layout(scene, &self.layout, transform);
+        for (x0, x1, line_index) in self.range_segments(self.preedit.range()) {
+            let Some(line) = self.layout.get(line_index) else {
+                continue;
+            };
+            let underline_y = line.metrics().baseline as f64 + 2.;
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                palette.foreground,
+                None,
+                &Rect::new(x0, underline_y, x1, underline_y + 1.),
+            );
+        }
+        if self.focused && caret.is_some() {
+            let caret_x = caret_x as f64;
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                palette.foreground,
+                None,
+                &Rect::new(caret_x, caret_top, caret_x + CARET_WIDTH, caret_bottom),
+            );
+        }
+        scene.pop_layer();
+    }
+
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
+        let width = match self.width {
+            Some(width) => width,
+            None if suggested_size.width.is_finite() => suggested_size.width,
+            None => DEFAULT_SIZE.width,
+        };
+        let height = match self.height {
+            Some(height) => height,
+            None if suggested_size.height.is_finite() => suggested_size.height,
+            None => DEFAULT_SIZE.height,
+        };
+        self.size = Size::new(width, height);
+        let wrap_width = (width - PADDING * 2.) as f32;
+        let mut layout = build_layout(
+            font_context,
+            palette,
+            &self.preedit.display_text(&self.text),
+            Brush::Solid(palette.foreground),
+        );
+        layout.break_all_lines(Some(wrap_width));
+        layout.align(Some(wrap_width), parley::layout::Alignment::Start);
+        self.layout = layout;
+        self.scroll_y = self.scroll_y.clamp(0., self.max_scroll());
+        self.size
+    }
+
+    fn children(&self) -> Vec<&WidgetData<State>> {
+        vec![]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
+        vec![]
+    }
+
+    fn event(
+        &mut self,
+        event_context: &mut event::EventContext,
+        event: event::WidgetEvent,
+        state: &mut State,
+    ) -> event::Status {
+        match event {
+            WidgetEvent::Keyboard(keyboard::Event::KeyPressed {
+                key,
+                text,
+                modifiers,
+                ..
+            }) => {
+                if key == Key::Named(NamedKey::Tab) || key == Key::Named(NamedKey::Escape) {
+                    return event::Status::Ignored;
+                }
+                let focus = self.selection.focus;
+                let changed = match clipboard_shortcut(
+                    &key,
+                    modifiers,
+                    &self.text,
+                    &self.selection,
+                    event_context.clipboard(),
+                    false,
+                ) {
+                    Some(insert) => {
+                        self.push_undo(EditKind::Other);
+                        replace_selection(&mut self.text, &mut self.selection, &insert);
+                        true
+                    }
+                    None => self.handle_key(&key, text.as_deref(), modifiers),
+                };
+                if changed || self.selection.focus != focus {
+                    self.scroll_to_caret = true;
+                }
+                if changed {
+                    let task = (self.on_change)(state, self.text.clone());
+                    event_context.push_task(task);
+                    event_context.state_changed = true;
+                }
+                event_context.repaint_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Ime(ime::Event::Preedit { text, cursor }) => {
+                self.preedit = Preedit {
+                    text,
+                    cursor,
+                    position: self.selection.focus,
+                };
+                self.scroll_to_caret = true;
+                event_context.layout_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Ime(ime::Event::Commit(text)) => {
+                self.preedit.clear();
+                self.push_undo(EditKind::Other);
+                replace_selection(&mut self.text, &mut self.selection, &text);
+                self.scroll_to_caret = true;
+                let task = (self.on_change)(state, self.text.clone());
+                event_context.push_task(task);
+                event_context.state_changed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Ime(ime::Event::Enabled | ime::Event::Disabled) => {
+                if !self.preedit.is_empty() {
+                    self.preedit.clear();
+                    event_context.layout_needed = true;
+                }
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Press {
+                position,
+                button: MouseButton::Left,
+            }) => {
+                event_context.request_focus();
+                self.selection = Selection::caret(self.hit_test(position));
+                self.goal_x = None;
+                self.last_edit = EditKind::Other;
+                self.is_dragging = true;
+                self.scroll_to_caret = true;
+                event_context.repaint_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Move { position }) => {
+                event_context.set_cursor(CursorIcon::Text);
+                if self.is_dragging {
+                    self.selection.focus = self.hit_test(position);
+                    self.scroll_to_caret = true;
+                    event_context.repaint_needed = true;
+                }
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Release {
+                button: MouseButton::Left,
+                ..
+            }) => {
+                self.is_dragging = false;
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Wheel { delta }) => {
+                let scroll_y = (self.scroll_y - delta.y).clamp(0., self.max_scroll());
+                if scroll_y == self.scroll_y {
+                    return event::Status::Ignored;
+                }
+                self.scroll_y = scroll_y;
+                event_context.repaint_needed = true;
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn set_hover(&mut self, hover: bool) -> event::Status {
+        self.hovered = hover;
+        if !hover {
+            self.is_dragging = false;
+        }
+        event::Status::Captured
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+
+    fn set_focus(&mut self, focus: bool) -> event::Status {
+        self.focused = focus;
+        event::Status::Captured
+    }
+
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        let caret = self
+            .preedit
+            .caret(self.selection.focus)
+            .unwrap_or(self.preedit.range().end);
+        let (caret_x, caret_line) = self.caret_position(caret);
+        let (top, bottom) = self.line_extent(caret_line);
+        let origin = self.text_origin();
+        Some(Rect::new(
+            origin.x + caret_x as f64,
+            origin.y + top,
+            origin.x + caret_x as f64 + CARET_WIDTH,
+            origin.y + bottom,
+        ))
+    }
+}
@@ -0,0 +1,455 @@
+use crate::event::ime;
+use crate::event::keyboard::{self, Key, NamedKey};
+use crate::event::{self, mouse, mouse::MouseButton, WidgetEvent};
+use crate::widget::text::{build_layout, paint_layout};
+use crate::widget::text_edit::{
+    clipboard_shortcut, next_char_boundary, next_word_boundary, prev_char_boundary,
+    prev_word_boundary, replace_selection, Preedit, Selection,
+};
+use crate::widget::{Widget, WidgetData, WidgetMarker};
+use crate::Palette;
+use crate::Task;
+use parley::layout::Cursor;
+use parley::{FontContext, Layout};
+use std::sync::Arc;
+use vello::peniko::kurbo::{Affine, Point, Rect, Size, Stroke};
+use vello::peniko::{BlendMode, Brush, Fill};
+use winit::window::CursorIcon;
+
+const TEXT_INPUT_HEIGHT: f64 = 34.;
+const PADDING: f64 = 9.;
+const RADIUS: f64 = 6.;
+const CARET_WIDTH: f64 = 1.;
+
+pub struct TextInput<State> {
+    pub(crate) width: f64,
+    pub(crate) on_change: Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>,
+    pub(crate) on_submit:
+        Option<Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>>,
+    text: String,
+    placeholder: String,
+    layout: Layout<Brush>,
+    placeholder_layout: Layout<Brush>,
+    selection: Selection,
+    preedit: Preedit,
+    scroll_offset: f64,
+    is_dragging: bool,
+    hovered: bool,
+    focused: bool,
+}
+
+impl<State> TextInput<State> {
+    pub fn new(
+        text: String,
+        placeholder: String,
+        width: f64,
+        on_change: Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>,
+        on_submit: Option<Arc<dyn Fn(&mut State, String) -> Task<State> + Send + Sync + 'static>>,
+    ) -> Self {
+        let selection = Selection::caret(text.len());
+        Self {
+            width,
+            on_change,
+            on_submit,
+            text,
+            placeholder,
+            layout: Layout::new(),
+            placeholder_layout: Layout::new(),
+            selection,
+            preedit: Preedit::default(),
+            scroll_offset: 0.,
+            is_dragging: false,
+            hovered: false,
+            focused: false,
+        }
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+        self.selection.clamp(&self.text);
+    }
+    pub fn set_placeholder(&mut self, placeholder: String) {
+        self.placeholder = placeholder;
+    }
+
+    fn text_origin(&self) -> Point {
+        Point::new(
+            PADDING - self.scroll_offset,
+            (TEXT_INPUT_HEIGHT - self.layout.height() as f64) / 2.,
+        )
+    }
+
+    /// Whether there is neither text nor preedit text to show
+    fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.preedit.is_empty()
+    }
+
+    /// Horizontal offset of the caret drawn before the byte at display index `idx`
+    fn caret_x(&self, idx: usize) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        Cursor::from_position(&self.layout, idx, true).offset as f64
+    }
+
+    fn hit_test(&self, position: Point) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let origin = self.text_origin();
+        let idx = Cursor::from_point(
+            &self.layout,
+            (position.x - origin.x) as f32,
+            self.layout.height() / 2.,
+        )
+        .insert_point;
+        self.preedit.from_display(idx)
+    }
+
+    /// Applies a key press to the text, returns true if the text was changed
+    fn handle_key(
+        &mut self,
+        key: &Key,
+        text: Option<&str>,
+        modifiers: keyboard::Modifiers,
+    ) -> bool {
+        let extend = modifiers.shift();
+        let collapsed = self.selection.is_collapsed();
+        match key {
+            Key::Named(NamedKey::ArrowLeft) => {
+                let idx = if !collapsed && !extend {
+                    self.selection.range().start
+                } else if modifiers.control() {
+                    prev_word_boundary(&self.text, self.selection.focus)
+                } else {
+                    prev_char_boundary(&self.text, self.selection.focus)
+                };
+                self.selection.move_to(idx, extend);
+                false
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                let idx = if !collapsed && !extend {
+                    self.selection.range().end
+                } else if modifiers.control() {
+                    next_word_boundary(&self.text, self.selection.focus)
+                } else {
+                    next_char_boundary(&self.text, self.selection.focus)
+                };
+                self.selection.move_to(idx, extend);
+                false
+            }
+            Key::Named(NamedKey::Home) => {
+                self.selection.move_to(0, extend);
+                false
+            }
+            Key::Named(NamedKey::End) => {
+                self.selection.move_to(self.text.len(), extend);
+                false
+            }
+            Key::Named(NamedKey::Backspace) => {
+                if collapsed {
+                    self.selection.anchor = if modifiers.control() {
+                        prev_word_boundary(&self.text, self.selection.focus)
+                    } else {
+                        prev_char_boundary(&self.text, self.selection.focus)
+                    };
+                }
+                let changed = !self.selection.is_collapsed();
+                replace_selection(&mut self.text, &mut self.selection, "");
+                changed
+            }
+            Key::Named(NamedKey::Delete) => {
+                if collapsed {
+                    self.selection.anchor = if modifiers.control() {
+                        next_word_boundary(&self.text, self.selection.focus)
+                    } else {
+                        next_char_boundary(&self.text, self.selection.focus)
+                    };
+                }
+                let changed = !self.selection.is_collapsed();
+                replace_selection(&mut self.text, &mut self.selection, "");
+                changed
+            }
+            Key::Character(character) if modifiers.control() && character == "a" => {
+                self.selection = Selection {
+                    anchor: 0,
+                    focus: self.text.len(),
+                };
+                false
+            }
+            _ => {
+                if modifiers.control() || modifiers.alt() {
+                    return false;
+                }
+                let insert = text
+                    .unwrap_or_default()
+                    .chars()
+                    .filter(|c| !c.is_control())
+                    .collect::<String>();
+                if insert.is_empty() {
+                    return false;
+                }
+                replace_selection(&mut self.text, &mut self.selection, &insert);
+                true
+            }
+        }
+    }
+}
+
+impl<State> WidgetMarker for TextInput<State> {}
+impl<State: 'static> Widget<State> for TextInput<State> {
+    fn debug_name(&self) -> &str {
+        "text_input"
+    }
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        let bounds = Rect::from_origin_size(Point::ZERO, Size::new(self.width, TEXT_INPUT_HEIGHT));
+        scene.fill(
+            Fill::NonZero,
+            Affine::default(),
+            palette
+                .foreground
+                .multiply_alpha(if self.hovered { 0.1 } else { 0.06 }),
+            None,
+            &bounds.to_rounded_rect(RADIUS),
+        );
+        if self.focused {
+            scene.stroke(
+                &Stroke::new(2.),
+                Affine::default(),
+                palette.accent.multiply_alpha(0.5),
+                None,
+                &bounds.inset(-1.).to_rounded_rect(RADIUS),
+            );
+        }
+
+        // keep the caret inside the visible part of the input
+        let visible_width = self.width - PADDING * 2.;
+        let caret = self.preedit.caret(self.selection.focus);
+        let caret_x = self.caret_x(caret.unwrap_or(self.preedit.range().end));
+        if caret_x - self.scroll_offset > visible_width {
+            self.scroll_offset = caret_x - visible_width;
+        } else if caret_x < self.scroll_offset {
+            self.scroll_offset = caret_x;
+        }
+        self.scroll_offset = self
+            .scroll_offset
+            .min((self.layout.width() as f64 - visible_width).max(0.));
+
+        scene.push_layer(
+            BlendMode::default(),
+            1.0,
+            Affine::default(),
+            &bounds.inset((-PADDING + CARET_WIDTH, 0.)),
+        );
+        let transform = Affine::translate(self.text_origin().to_vec2());
+        let line_height = self.layout.height() as f64;
+        if !self.selection.is_collapsed() {
+            let range = self.selection.range();
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                palette.accent.multiply_alpha(0.3),
+                None,
+                &Rect::new(
+                    self.caret_x(self.preedit.to_display(range.start)),
+                    0.,
+                    self.caret_x(self.preedit.to_display(range.end)),
+                    line_height,
+                ),
+            );
+        }
+        if self.is_empty() {
+            paint_layout(scene, &self.placeholder_layout, transform);
+        } else {
+            paint_layout(scene, &self.layout, transform);
+        }
+        if !self.preedit.is_empty() {
+            let range = self.preedit.range();
+            let underline_y = self
+                .layout
+                .get(0)
+                .map(|line| line.metrics().baseline as f64 + 2.)
+                .unwrap_or(line_height);
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                palette.foreground,
+                None,
+                &Rect::new(
+                    self.caret_x(range.start),
+                    underline_y,
+                    self.caret_x(range.end),
+                    underline_y + 1.,
+                ),
+            );
+        }
+        if self.focused && caret.is_some() {
+            let line_height = line_height.max(self.placeholder_layout.height() as f64);
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                palette.foreground,
+                None,
+                &Rect::new(caret_x, 0., caret_x + CARET_WIDTH, line_height),
+            );
+        }
+        scene.pop_layer();
+    }
+
+    fn layout(&mut self, _: Size, font_context: &mut FontContext, palette: &Palette) -> Size {
+        let mut layout = build_layout(
+            font_context,
+            palette,
+            &self.preedit.display_text(&self.text),
+            Brush::Solid(palette.foreground),
+        );
+        layout.break_all_lines(None);
+        layout.align(None, parley::layout::Alignment::Start);
+        self.layout = layout;
+        let mut placeholder_layout = build_layout(
+            font_context,
+            palette,
+            &self.placeholder,
+            Brush::Solid(palette.foreground.multiply_alpha(0.5)),
+        );
+        placeholder_layout.break_all_lines(None);
+        placeholder_layout.align(None, parley::layout::Alignment::Start);
+        self.placeholder_layout = placeholder_layout;
+        Size::new(self.width, TEXT_INPUT_HEIGHT)
+    }
+
+    fn children(&self) -> Vec<&WidgetData<State>> {
+        vec![]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
+        vec![]
+    }
+
+    fn event(
+        &mut self,
+        event_context: &mut event::EventContext,
+        event: event::WidgetEvent,
+        state: &mut State,
+    ) -> event::Status {
+        match event {
+            WidgetEvent::Keyboard(keyboard::Event::KeyPressed {
+                key,
+                text,
+                modifiers,
+                ..
+            }) => {
+                if key == Key::Named(NamedKey::Enter) {
+                    if let Some(on_submit) = &self.on_submit {
+                        let task = (on_submit)(state, self.text.clone());
+                        event_context.push_task(task);
+                        event_context.state_changed = true;
+                    }
+                    return event::Status::Captured;
+                }
+                if key == Key::Named(NamedKey::Tab) || key == Key::Named(NamedKey::Escape) {
+                    return event::Status::Ignored;
+                }
+                let changed = match clipboard_shortcut(
+                    &key,
+                    modifiers,
+                    &self.text,
+                    &self.selection,
+                    event_context.clipboard(),
+                    true,
+                ) {
+                    Some(insert) => {
+                        replace_selection(&mut self.text, &mut self.selection, &insert);
+                        true
+                    }
+                    None => self.handle_key(&key, text.as_deref(), modifiers),
+                };
+                if changed {
+                    let task = (self.on_change)(state, self.text.clone());
+                    event_context.push_task(task);
+                    event_context.state_changed = true;
+                }
+                event_context.repaint_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Ime(ime::Event::Preedit { text, cursor }) => {
+                self.preedit = Preedit {
+                    text,
+                    cursor,
+                    position: self.selection.focus,
+                };
+                event_context.layout_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Ime(ime::Event::Commit(text)) => {
+                self.preedit.clear();
+                replace_selection(&mut self.text, &mut self.selection, &text);
+                let task = (self.on_change)(state, self.text.clone());
+                event_context.push_task(task);
+                event_context.state_changed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Ime(ime::Event::Enabled | ime::Event::Disabled) => {
+                if !self.preedit.is_empty() {
+                    self.preedit.clear();
+                    event_context.layout_needed = true;
+                }
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Press {
+                position,
+                button: MouseButton::Left,
+            }) => {
+                event_context.request_focus();
+                self.selection = Selection::caret(self.hit_test(position));
+                self.is_dragging = true;
+                event_context.repaint_needed = true;
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Move { position }) => {
+                event_context.set_cursor(CursorIcon::Text);
+                if self.is_dragging {
+                    self.selection.focus = self.hit_test(position);
+                    event_context.repaint_needed = true;
+                }
+                event::Status::Captured
+            }
+            WidgetEvent::Mouse(mouse::Event::Release {
+                button: MouseButton::Left,
+                ..
+            }) => {
+                self.is_dragging = false;
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn set_hover(&mut self, hover: bool) -> event::Status {
+        self.hovered = hover;
+        if !hover {
+            self.is_dragging = false;
+        }
+        event::Status::Captured
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+
+    fn set_focus(&mut self, focus: bool) -> event::Status {
+        self.focused = focus;
+        event::Status::Captured
+    }
+
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        let caret = self
+            .preedit
+            .caret(self.selection.focus)
+            .unwrap_or(self.preedit.range().end);
+        let x = self.text_origin().x + self.caret_x(caret);
+        Some(Rect::new(x, 0., x + CARET_WIDTH, TEXT_INPUT_HEIGHT))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{event, AsAny};
+use crate::{event, AsAny, Palette};
 use parley::FontContext;
 use vello::{
     kurbo::Affine,
@@ -8,7 +8,12 @@ use vello::{
 pub trait WidgetMarker {}
 
 pub trait Widget<State: 'static>: AsAny {
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size;
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size;
     fn event(
         &mut self,
         event_context: &mut event::EventContext,
@@ -16,7 +21,21 @@ pub trait Widget<State: 'static>: AsAny {
         state: &mut State,
     ) -> event::Status;
     fn set_hover(&mut self, hover: bool) -> event::Status;
-    fn paint(&mut self, scene: &mut vello::Scene);
+    /// Whether the widget can receive keyboard focus, either with Tab traversal
+    /// or by calling [`EventContext::request_focus`](event::EventContext::request_focus)
+    fn accepts_focus(&self) -> bool {
+        false
+    }
+    fn set_focus(&mut self, _focus: bool) -> event::Status {
+        event::Status::Ignored
+    }
+    /// Area of the text caret relative to the widget, for widgets that take text input.
+    /// While a widget returning `Some` is focused the window enables IME and places
+    /// the candidate window next to this area
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        None
+    }
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette);
     fn children(&self) -> Vec<&WidgetData<State>>;
     fn children_mut(&mut self) -> Vec<&mut WidgetData<State>>;
     fn debug_name(&self) -> &str;
@@ -36,6 +55,55 @@ pub trait Widget<State: 'static>: AsAny {
         v
     }
 }
+/// Element of a type erased [`AnyWidgetView`](crate::view::AnyWidgetView), forwards
+/// everything to the boxed widget
+impl<State: 'static> WidgetMarker for Box<dyn Widget<State>> {}
+impl<State: 'static> Widget<State> for Box<dyn Widget<State>> {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
+        (**self).layout(suggested_size, font_context, palette)
+    }
+    fn event(
+        &mut self,
+        event_context: &mut event::EventContext,
+        event: event::WidgetEvent,
+        state: &mut State,
+    ) -> event::Status {
+        (**self).event(event_context, event, state)
+    }
+    fn set_hover(&mut self, hover: bool) -> event::Status {
+        (**self).set_hover(hover)
+    }
+    fn accepts_focus(&self) -> bool {
+        (**self).accepts_focus()
+    }
+    fn set_focus(&mut self, focus: bool) -> event::Status {
+        (**self).set_focus(focus)
+    }
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        (**self).ime_cursor_area()
+    }
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        (**self).paint(scene, palette)
+    }
+    fn children(&self) -> Vec<&WidgetData<State>> {
+        (**self).children()
+    }
+    fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
+        (**self).children_mut()
+    }
+    fn debug_name(&self) -> &str {
+        (**self).debug_name()
+    }
+    fn bounds_tree(&self, id_path: WidgetIdPath, position: Point) -> Vec<(WidgetIdPath, Rect)> {
+        (**self).bounds_tree(id_path, position)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ChangeFlags {
     pub needs_layout: bool,
@@ -87,22 +155,27 @@ impl<State: 'static> WidgetData<State> {
         self.id = id;
         self
     }
-    pub fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    pub fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         if !self.change_flags.needs_layout {
             tracing::warn!("Widget didn't need layout, but it was ignored");
         }
 
         // FIXME: not every layout requires a repaint
         self.change_flags.needs_paint = true;
-        self.inner.layout(suggested_size, font_context)
+        self.inner.layout(suggested_size, font_context, palette)
     }
-    pub fn paint(&mut self, scene: &mut vello::Scene) {
+    pub fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         if self.change_flags.needs_paint {
             tracing::warn!("Widget didn't need layout, but it was ignored");
         }
         self.change_flags.needs_paint = false;
         self.scene.reset();
-        self.inner.paint(&mut self.scene);
+        self.inner.paint(&mut self.scene, palette);
         let transform = Affine::translate(self.position.to_vec2());
         scene.append(&self.scene, Some(transform));
     }
@@ -2,15 +2,17 @@ use crate::event;
 use crate::event::mouse::MouseButton;
 use crate::event::WidgetEvent;
 use crate::widget::{Header, Widget, WidgetData, WidgetMarker};
+use crate::Palette;
+use crate::Task;
 use core::f64::consts::PI;
 use parley::FontContext;
+use std::sync::Arc;
 use vello::peniko::kurbo::{Affine, Circle, Point, Rect, RoundedRect, Shape, Size};
 use vello::peniko::{BlendMode, Brush, Color, Fill, Gradient};
 use winit::window::ResizeDirection;
 const CORNER_RADIUS: f64 = 12.;
 const SHADOW_WIDTH: f64 = 15.;
 const HEADER_BAR_HEIGHT: f64 = 46.;
-const SHADOW_COLOR: Color = Color::rgba8(0, 0, 0, 100);
 const SHADOW_FADE_COLOR: Color = Color::rgba8(0, 0, 0, 0);
 
 fn linear_component(u: f64) -> f64 {
@@ -33,13 +35,82 @@ fn interpolate(start: f64, end: f64, factor: f64) -> f64 {
         (linear_component(end) - linear_component(start)) * factor + linear_component(start),
     )
 }
-#[derive(Debug)]
+
+/// Who draws the title bar and border of a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Decorations {
+    /// Ralaire draws a header bar, rounded corners and a shadow
+    #[default]
+    ClientSide,
+    /// The window manager draws its own title bar, the content fills the window
+    ServerSide,
+    /// No title bar or border at all
+    None,
+}
+
+/// Raw RGBA pixels of a window icon
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowIcon {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Settings of the native window, set through the builder methods of the `window` view.
+/// Sizes and positions are in logical pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSettings {
+    pub inner_size: Option<Size>,
+    pub min_inner_size: Option<Size>,
+    pub max_inner_size: Option<Size>,
+    pub position: Option<Point>,
+    pub resizable: bool,
+    /// How far the corners of the resize border reach along the window edges
+    pub resize_grab_width: f64,
+    pub always_on_top: bool,
+    pub fullscreen: bool,
+    pub maximized: bool,
+    pub decorations: Decorations,
+    pub transparent: bool,
+    /// Application id on Wayland, it can't be changed once the window is open
+    pub app_id: Option<String>,
+    pub icon: Option<WindowIcon>,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            inner_size: None,
+            min_inner_size: Some(Size::new(200., 200.)),
+            max_inner_size: None,
+            position: None,
+            resizable: true,
+            resize_grab_width: CORNER_RADIUS + SHADOW_WIDTH,
+            always_on_top: false,
+            fullscreen: false,
+            maximized: false,
+            decorations: Decorations::ClientSide,
+            transparent: true,
+            app_id: None,
+            icon: None,
+        }
+    }
+}
+
 pub struct Window<State: 'static> {
     bounds: RoundedRect,
     size: Size, // includes shadows
     pub header: WidgetData<State>,
     pub content: WidgetData<State>,
     pub title: String,
+    pub(crate) show_header: bool,
+    pub(crate) settings: WindowSettings,
+    pub(crate) on_close: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
+    /// Set by the runtime when the window manager draws decorations around the window
+    pub(crate) server_decorated: bool,
+    /// Kept up to date by the runtime, both remove the shadow and rounded corners
+    pub(crate) maximized: bool,
+    pub(crate) fullscreen: bool,
 }
 
 impl<State> Window<State> {
@@ -47,6 +118,9 @@ impl<State> Window<State> {
         header: Header<State>,
         content: Content,
         title: String,
+        show_header: bool,
+        settings: WindowSettings,
+        on_close: Option<Arc<dyn Fn(&mut State) -> Task<State> + Send + Sync + 'static>>,
     ) -> Self {
         Window {
             bounds: Rect::ZERO.to_rounded_rect(CORNER_RADIUS),
@@ -54,43 +128,91 @@ impl<State> Window<State> {
             header: WidgetData::new(Box::new(header)),
             content: WidgetData::new(Box::new(content)),
             title,
+            show_header,
+            settings,
+            on_close,
+            server_decorated: false,
+            maximized: false,
+            fullscreen: false,
         }
     }
-}
 
-impl<State> WidgetMarker for Window<State> {}
-impl<State: 'static> Widget<State> for Window<State> {
-    fn debug_name(&self) -> &str {
-        "window"
+    /// The decorations actually in use, client side ones fall back to server side
+    /// decorations when the compositor insists on drawing its own
+    pub fn decorations(&self) -> Decorations {
+        if self.server_decorated && self.settings.decorations != Decorations::ServerSide {
+            Decorations::ServerSide
+        } else {
+            self.settings.decorations
+        }
     }
-    // TODO: Support SSD on wayland
-    // TODO: Figure out what to do for other platforms
-    fn paint(&mut self, scene: &mut vello::Scene) {
+
+    /// Client side decorated windows that aren't maximized or fullscreen get a shadow,
+    /// rounded corners and a resize border
+    // TODO: Tiled windows should lose them too, but winit 0.30 doesn't report the
+    // xdg_toplevel tiled states, so they are treated as floating for now
+    fn is_floating(&self) -> bool {
+        self.decorations() == Decorations::ClientSide && !self.maximized && !self.fullscreen
+    }
+
+    /// Server side decorations come with their own title bar
+    fn has_header(&self) -> bool {
+        self.show_header && self.decorations() == Decorations::ClientSide
+    }
+
+    /// Which way the window gets resized when dragged at `position`. Everything outside
+    /// of the window bounds is resize border, corners reach `resize_grab_width` along
+    /// the edges from the window bounds
+    fn resize_direction(&self, position: Point) -> Option<ResizeDirection> {
+        if !self.is_floating() || !self.settings.resizable || self.bounds.contains(position) {
+            return None;
+        }
+        let bounds = self.bounds.rect();
+        let grab_width = self.settings.resize_grab_width;
+        let west = position.x < bounds.x0 + grab_width;
+        let east = position.x > bounds.x1 - grab_width;
+        let north = position.y < bounds.y0 + grab_width;
+        let south = position.y > bounds.y1 - grab_width;
+        match (north, south, west, east) {
+            (true, _, true, _) => Some(ResizeDirection::NorthWest),
+            (true, _, _, true) => Some(ResizeDirection::NorthEast),
+            (_, true, true, _) => Some(ResizeDirection::SouthWest),
+            (_, true, _, true) => Some(ResizeDirection::SouthEast),
+            _ if position.y < bounds.y0 => Some(ResizeDirection::North),
+            _ if position.y > bounds.y1 => Some(ResizeDirection::South),
+            _ if position.x < bounds.x0 => Some(ResizeDirection::West),
+            _ if position.x > bounds.x1 => Some(ResizeDirection::East),
+            // cut off by a rounded corner, but not close enough to the edges to be a corner
+            _ => None,
+        }
+    }
+
+    fn paint_shadow(&self, scene: &mut vello::Scene, shadow_color: Color) {
         // Normally shadows are implemented with blur, vello doesn't support it yet so
         // here we approximate the gaussian function exp(-8x^2) by using 11 color points
-        // and linear interpolation between the SHADOW_COLOR and SHADOW_FADE_COLOR
+        // and linear interpolation between the shadow colour and SHADOW_FADE_COLOR
         let shadow_color_stops = [
-            (0., SHADOW_COLOR),
+            (0., shadow_color),
             (
                 0.1,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.9231,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.9231,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.9231,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.9231,
                     ),
@@ -100,22 +222,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.2,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.7261,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.7261,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.7261,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.7261,
                     ),
@@ -125,22 +247,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.3,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.4868,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.4868,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.4868,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.4868,
                     ),
@@ -150,22 +272,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.4,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.2780,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.2780,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.2780,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.2780,
                     ),
@@ -175,22 +297,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.5,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.1353,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.1353,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.1353,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.1353,
                     ),
@@ -200,22 +322,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.6,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.056135,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.056135,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.056135,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.056135,
                     ),
@@ -225,22 +347,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.7,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 0.019841,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 0.019841,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 0.019841,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 0.019841,
                     ),
@@ -250,22 +372,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.8,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 5.9760e-03,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 5.9760e-03,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 5.9760e-03,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 5.9760e-03,
                     ),
@@ -275,22 +397,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 0.9,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 1.5338e-03,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 1.5338e-03,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 1.5338e-03,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 1.5338e-03,
                     ),
@@ -300,22 +422,22 @@ impl<State: 'static> Widget<State> for Window<State> {
                 1.0,
                 Color::rgba(
                     interpolate(
-                        SHADOW_COLOR.r as f64 / 255.,
+                        shadow_color.r as f64 / 255.,
                         SHADOW_FADE_COLOR.r as f64 / 255.,
                         1.0 - 3.3546e-04,
                     ),
                     interpolate(
-                        SHADOW_COLOR.g as f64 / 255.,
+                        shadow_color.g as f64 / 255.,
                         SHADOW_FADE_COLOR.g as f64 / 255.,
                         1.0 - 3.3546e-04,
                     ),
                     interpolate(
-                        SHADOW_COLOR.b as f64 / 255.,
+                        shadow_color.b as f64 / 255.,
                         SHADOW_FADE_COLOR.b as f64 / 255.,
                         1.0 - 3.3546e-04,
                     ),
                     interpolate(
-                        SHADOW_COLOR.a as f64 / 255.,
+                        shadow_color.a as f64 / 255.,
                         SHADOW_FADE_COLOR.a as f64 / 255.,
                         1.0 - 3.3546e-04,
                     ),
@@ -525,40 +647,68 @@ impl<State: 'static> Widget<State> for Window<State> {
             )
             .segment(0., 1. / 2. * PI, 1. / 2. * PI),
         );
+    }
+}
 
+impl<State> WidgetMarker for Window<State> {}
+impl<State: 'static> Widget<State> for Window<State> {
+    fn debug_name(&self) -> &str {
+        "window"
+    }
+    // TODO: Figure out what to do for other platforms
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
+        if self.is_floating() {
+            self.paint_shadow(scene, palette.shadow);
+        }
         scene.fill(
             Fill::NonZero,
             Affine::default(),
-            Color::WHITE,
+            palette.background,
             None,
             &self.bounds,
         );
         scene.push_layer(BlendMode::default(), 1.0, Affine::default(), &self.bounds);
-        self.header.paint(scene);
-        self.content.paint(scene);
+        if self.has_header() {
+            self.header.paint(scene, palette);
+        }
+        self.content.paint(scene, palette);
         scene.pop_layer();
     }
 
-    fn layout(&mut self, suggested_size: Size, font_context: &mut FontContext) -> Size {
+    fn layout(
+        &mut self,
+        suggested_size: Size,
+        font_context: &mut FontContext,
+        palette: &Palette,
+    ) -> Size {
         self.size = suggested_size;
+        let (shadow_width, corner_radius) = if self.is_floating() {
+            (SHADOW_WIDTH, CORNER_RADIUS)
+        } else {
+            (0., 0.)
+        };
         self.bounds = Rect::from_origin_size(
-            Point::new(SHADOW_WIDTH, SHADOW_WIDTH),
-            suggested_size - Size::new(SHADOW_WIDTH * 2., SHADOW_WIDTH * 2.),
+            Point::new(shadow_width, shadow_width),
+            suggested_size - Size::new(shadow_width * 2., shadow_width * 2.),
         )
-        .to_rounded_rect(CORNER_RADIUS);
-        let header_size = Size::new(self.bounds.width(), HEADER_BAR_HEIGHT);
-        let content_size = Size::new(
-            self.bounds.width(),
-            self.bounds.height() - HEADER_BAR_HEIGHT,
-        );
+        .to_rounded_rect(corner_radius);
+        let header_height = if self.has_header() {
+            HEADER_BAR_HEIGHT
+        } else {
+            0.
+        };
+        let header_size = Size::new(self.bounds.width(), header_height);
+        let content_size = Size::new(self.bounds.width(), self.bounds.height() - header_height);
 
-        self.header.layout(header_size, font_context);
+        if self.has_header() {
+            self.header.layout(header_size, font_context, palette);
+        }
 
-        self.content.layout(content_size, font_context);
+        self.content.layout(content_size, font_context, palette);
         self.header.size = header_size;
-        self.header.position = Point::new(SHADOW_WIDTH, SHADOW_WIDTH);
+        self.header.position = Point::new(shadow_width, shadow_width);
         self.content.size = content_size;
-        self.content.position = Point::new(SHADOW_WIDTH, SHADOW_WIDTH + HEADER_BAR_HEIGHT);
+        self.content.position = Point::new(shadow_width, shadow_width + header_height);
         suggested_size
     }
     fn event(
@@ -568,56 +718,150 @@ impl<State: 'static> Widget<State> for Window<State> {
         _state: &mut State,
     ) -> event::Status {
         event_context.winit_window.set_title(&self.title);
-        if let WidgetEvent::Mouse(event::mouse::Event::Press { position, button }) = event {
-            if button == MouseButton::Left && !self.bounds.contains(position) {
-                let x = (position.x / (self.size.width / 3.)) as u8;
-                let y = (position.y / (self.size.height / 3.)) as u8;
-                if x == 0 && y == 0 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::NorthWest);
-                } else if x == 1 && y == 0 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::North);
-                } else if x == 2 && y == 0 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::NorthEast);
-                } else if x == 2 && y == 1 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::East);
-                } else if x == 2 && y == 2 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::SouthEast);
-                } else if x == 1 && y == 2 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::South);
-                } else if x == 0 && y == 2 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::SouthWest);
-                } else if x == 0 && y == 1 {
-                    let _ = event_context
-                        .winit_window
-                        .drag_resize_window(ResizeDirection::West);
+        match event {
+            WidgetEvent::Mouse(event::mouse::Event::Move { position }) => {
+                if let Some(direction) = self.resize_direction(position) {
+                    event_context.set_cursor(direction.into());
+                    return event::Status::Captured;
                 }
-                return event::Status::Captured;
             }
+            WidgetEvent::Mouse(event::mouse::Event::Press {
+                position,
+                button: MouseButton::Left,
+            }) => {
+                if let Some(direction) = self.resize_direction(position) {
+                    let _ = event_context.winit_window.drag_resize_window(direction);
+                    return event::Status::Captured;
+                }
+            }
+            _ => {}
         }
-
         event::Status::Ignored
     }
     fn children(&self) -> Vec<&WidgetData<State>> {
+        if !self.has_header() {
+            return vec![&self.content];
+        }
         vec![&self.header, &self.content]
     }
     fn children_mut(&mut self) -> Vec<&mut WidgetData<State>> {
+        if !self.has_header() {
+            return vec![&mut self.content];
+        }
         vec![&mut self.header, &mut self.content]
     }
     fn set_hover(&mut self, _hover: bool) -> event::Status {
         event::Status::Ignored
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{Text, TextStyle, WindowControls};
+
+    /// A floating window whose bounds span from (15, 15) to (415, 315)
+    fn window(settings: WindowSettings) -> Window<()> {
+        let header = Header::new(
+            Text::new(String::new(), TextStyle::default()),
+            Text::new(String::new(), TextStyle::default()),
+            Text::new(String::new(), TextStyle::default()),
+            WindowControls::new(&[]),
+            WindowControls::new(&[]),
+        );
+        let mut window = Window::new(
+            header,
+            Text::new(String::new(), TextStyle::default()),
+            String::new(),
+            true,
+            settings,
+            None,
+        );
+        window.bounds = Rect::new(15., 15., 415., 315.).to_rounded_rect(CORNER_RADIUS);
+        window
+    }
+
+    #[test]
+    fn resize_direction_corners() {
+        let window = window(WindowSettings::default());
+        let direction = |x, y| window.resize_direction(Point::new(x, y));
+        assert_eq!(direction(5., 5.), Some(ResizeDirection::NorthWest));
+        assert_eq!(direction(420., 5.), Some(ResizeDirection::NorthEast));
+        assert_eq!(direction(5., 320.), Some(ResizeDirection::SouthWest));
+        assert_eq!(direction(420., 320.), Some(ResizeDirection::SouthEast));
+        // cut off by the rounded corner, inside the window rectangle
+        assert_eq!(direction(16., 16.), Some(ResizeDirection::NorthWest));
+    }
+
+    #[test]
+    fn resize_direction_edges() {
+        let window = window(WindowSettings::default());
+        let direction = |x, y| window.resize_direction(Point::new(x, y));
+        assert_eq!(direction(200., 5.), Some(ResizeDirection::North));
+        assert_eq!(direction(200., 320.), Some(ResizeDirection::South));
+        assert_eq!(direction(5., 150.), Some(ResizeDirection::West));
+        assert_eq!(direction(420., 150.), Some(ResizeDirection::East));
+        assert_eq!(direction(200., 150.), None);
+    }
+
+    #[test]
+    fn resize_direction_corners_reach_resize_grab_width_along_the_edges() {
+        let default = window(WindowSettings::default());
+        // the default grab width is 27, so corners reach down to y = 42
+        assert_eq!(
+            default.resize_direction(Point::new(5., 40.)),
+            Some(ResizeDirection::NorthWest)
+        );
+        assert_eq!(
+            default.resize_direction(Point::new(5., 44.)),
+            Some(ResizeDirection::West)
+        );
+
+        let narrow = window(WindowSettings {
+            resize_grab_width: 5.,
+            ..Default::default()
+        });
+        assert_eq!(
+            narrow.resize_direction(Point::new(5., 40.)),
+            Some(ResizeDirection::West)
+        );
+        assert_eq!(
+            narrow.resize_direction(Point::new(5., 18.)),
+            Some(ResizeDirection::NorthWest)
+        );
+
+        let none = window(WindowSettings {
+            resize_grab_width: 0.,
+            ..Default::default()
+        });
+        assert_eq!(none.resize_direction(Point::new(16., 16.)), None);
+    }
+
+    #[test]
+    fn resize_direction_none_when_not_resizable() {
+        let window = window(WindowSettings {
+            resizable: false,
+            ..Default::default()
+        });
+        assert_eq!(window.resize_direction(Point::new(5., 5.)), None);
+        assert_eq!(window.resize_direction(Point::new(200., 5.)), None);
+    }
+
+    #[test]
+    fn resize_direction_none_when_not_floating() {
+        let mut maximized = window(WindowSettings::default());
+        maximized.maximized = true;
+        assert_eq!(maximized.resize_direction(Point::new(5., 5.)), None);
+
+        let mut fullscreen = window(WindowSettings::default());
+        fullscreen.fullscreen = true;
+        assert_eq!(fullscreen.resize_direction(Point::new(200., 5.)), None);
+
+        let mut server_decorated = window(WindowSettings::default());
+        server_decorated.server_decorated = true;
+        assert_eq!(
+            server_decorated.resize_direction(Point::new(5., 150.)),
+            None
+        );
+    }
+}
@@ -3,10 +3,11 @@ use crate::widget::alignment;
 use crate::widget::Container;
 use crate::widget::{Widget, WidgetData, WidgetMarker};
 use crate::InternalMessage;
+use crate::{Padding, Palette};
 use parley::FontContext;
 use vello::kurbo::Affine;
 use vello::peniko::kurbo::{Circle, Point, Size};
-use vello::peniko::{Color, Fill};
+use vello::peniko::Fill;
 
 const SIZE: Size = Size::new(24., 24.);
 const CENTER: Point = Point::new(SIZE.width / 2., SIZE.height / 2.);
@@ -23,7 +24,7 @@ impl<State: 'static> WindowButtonWidget<State> {
             child,
             alignment::Horizontal::Center,
             alignment::Vertical::Center,
-            0.0.into(),
+            Some(Padding::ZERO),
         );
         Self {
             on_press,
@@ -38,11 +39,11 @@ impl<State: 'static> Widget<State> for WindowButtonWidget<State> {
     fn debug_name(&self) -> &str {
         "window_button"
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         scene.fill(
             Fill::NonZero,
             Affine::default(),
-            Color::rgb8(235, 235, 235),
+            palette.window_button,
             None,
             &Circle::new(CENTER, RADIUS),
         );
@@ -50,7 +51,7 @@ impl<State: 'static> Widget<State> for WindowButtonWidget<State> {
             scene.fill(
                 Fill::NonZero,
                 Affine::default(),
-                Color::rgb8(223, 223, 223),
+                palette.window_button_hover,
                 None,
                 &Circle::new(CENTER, RADIUS),
             );
@@ -61,7 +62,7 @@ impl<State: 'static> Widget<State> for WindowButtonWidget<State> {
         //     Affine::default(),
         //     &SIZE.to_rect(),
         // );
-        self.child.paint(scene);
+        self.child.paint(scene, palette);
         // scene.push_layer(
         //     BlendMode::new(Mix::Normal, Compose::SrcAtop),
         //     1.0,
@@ -87,8 +88,8 @@ impl<State: 'static> Widget<State> for WindowButtonWidget<State> {
         self.child.children_mut()
     }
 
-    fn layout(&mut self, _: Size, font_context: &mut FontContext) -> Size {
-        self.child.layout(SIZE, font_context);
+    fn layout(&mut self, _: Size, font_context: &mut FontContext, palette: &Palette) -> Size {
+        self.child.layout(SIZE, font_context, palette);
         SIZE
     }
 
@@ -1,13 +1,57 @@
 use crate::widget::alignment;
 use crate::widget::{Container, Svg, Widget, WidgetData, WidgetMarker, WindowButtonWidget};
 use crate::{event, InternalMessage};
+use crate::{Padding, Palette};
 use parley::FontContext;
 use vello::peniko::kurbo::{Point, Size};
 use vello_svg::usvg;
 
-const WINDOW_CONTROLS_WIDTH: f64 = 100.;
+const BUTTON_WIDTH: f64 = 100. / 3.;
 const WINDOW_CONTROLS_HEIGHT: f64 = 46.;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowButton {
+    Minimize,
+    Maximize,
+    Close,
+}
+
+/// Which window buttons are shown on which side of the header, in the format of
+/// GNOME's `button-layout` setting: the buttons of the start and end separated
+/// by a colon, for example `close,minimize:` or `:minimize,maximize,close`.
+/// Unknown button names are skipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonLayout {
+    pub start: Vec<WindowButton>,
+    pub end: Vec<WindowButton>,
+}
+
+impl Default for ButtonLayout {
+    fn default() -> Self {
+        ":minimize,maximize,close".into()
+    }
+}
+
+impl From<&str> for ButtonLayout {
+    fn from(layout: &str) -> Self {
+        let buttons = |side: &str| {
+            side.split(',')
+                .filter_map(|name| match name.trim() {
+                    "minimize" => Some(WindowButton::Minimize),
+                    "maximize" => Some(WindowButton::Maximize),
+                    "close" => Some(WindowButton::Close),
+                    _ => None,
+                })
+                .collect()
+        };
+        let (start, end) = layout.split_once(':').unwrap_or((layout, ""));
+        Self {
+            start: buttons(start),
+            end: buttons(end),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct WindowControls<State: 'static> {
     // TODO: Support 'restore' icon on maximise button
@@ -20,62 +64,53 @@ pub struct WindowControls<State: 'static> {
 }
 
 impl<State: 'static> WindowControls<State> {
-    pub fn new() -> Self {
-        tracing::warn!("Creating window control buttons!!");
-        let close_svg = usvg::Tree::from_str(
+    pub fn new(buttons: &[WindowButton]) -> Self {
+        let mut window_controls = Self { buttons: vec![] };
+        window_controls.set_buttons(buttons);
+        window_controls
+    }
+
+    pub fn set_buttons(&mut self, buttons: &[WindowButton]) {
+        self.buttons = buttons.iter().copied().map(window_button).collect();
+    }
+
+    /// The width the buttons take up in the header
+    pub fn width(&self) -> f64 {
+        BUTTON_WIDTH * self.buttons.len() as f64
+    }
+}
+
+fn window_button<State: 'static>(button: WindowButton) -> WidgetData<State> {
+    let (icon, on_press) = match button {
+        WindowButton::Minimize => (
             include_str!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
-                "/assets/icons/window-close-symbolic.svg"
+                "/assets/icons/window-minimize-symbolic.svg"
             )),
-            &usvg::Options::default(),
-        )
-        .unwrap();
-        let maximize_svg = usvg::Tree::from_str(
+            InternalMessage::MinimiseWindow,
+        ),
+        WindowButton::Maximize => (
             include_str!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
                 "/assets/icons/window-maximize-symbolic.svg"
             )),
-            &usvg::Options::default(),
-        )
-        .unwrap();
-        let minimize_svg = usvg::Tree::from_str(
+            InternalMessage::MaximiseWindow,
+        ),
+        WindowButton::Close => (
             include_str!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
-                "/assets/icons/window-minimize-symbolic.svg"
+                "/assets/icons/window-close-symbolic.svg"
             )),
-            &usvg::Options::default(),
-        )
-        .unwrap();
-        let close_icon = Svg::new(close_svg);
-        let close_button = WindowButtonWidget::new(close_icon, InternalMessage::CloseWindow);
-        let close_button = WidgetData::new(Box::new(Container::new(
-            close_button,
-            alignment::Horizontal::Center,
-            alignment::Vertical::Center,
-            0.0.into(),
-        )));
-        let maximise_icon = Svg::new(maximize_svg);
-        let maximise_button =
-            WindowButtonWidget::new(maximise_icon, InternalMessage::MaximiseWindow);
-        let maximise_button = WidgetData::new(Box::new(Container::new(
-            maximise_button,
-            alignment::Horizontal::Center,
-            alignment::Vertical::Center,
-            0.0.into(),
-        )));
-        let minimise_icon = Svg::new(minimize_svg);
-        let minimise_button =
-            WindowButtonWidget::new(minimise_icon, InternalMessage::MinimiseWindow);
-        let minimise_button = WidgetData::new(Box::new(Container::new(
-            minimise_button,
-            alignment::Horizontal::Center,
-            alignment::Vertical::Center,
-            0.0.into(),
-        )));
-        Self {
-            buttons: vec![close_button, maximise_button, minimise_button],
-        }
-    }
+            InternalMessage::CloseWindow,
+        ),
+    };
+    let icon = Svg::new(usvg::Tree::from_str(icon, &usvg::Options::default()).unwrap());
+    WidgetData::new(Box::new(Container::new(
+        WindowButtonWidget::new(icon, on_press),
+        alignment::Horizontal::Center,
+        alignment::Vertical::Center,
+        Some(Padding::ZERO),
+    )))
 }
 
 impl<State> WidgetMarker for WindowControls<State> {}
@@ -83,22 +118,17 @@ impl<State: 'static> Widget<State> for WindowControls<State> {
     fn debug_name(&self) -> &str {
         "window_controls"
     }
-    fn layout(&mut self, _: Size, font_context: &mut FontContext) -> Size {
-        let button_size_hint = Size::new(WINDOW_CONTROLS_WIDTH / 3., WINDOW_CONTROLS_HEIGHT);
-        let number_of_buttons = self.buttons.len();
+    fn layout(&mut self, _: Size, font_context: &mut FontContext, palette: &Palette) -> Size {
+        let button_size_hint = Size::new(BUTTON_WIDTH, WINDOW_CONTROLS_HEIGHT);
         for (idx, button) in self.buttons.iter_mut().enumerate() {
-            button.size = button.layout(button_size_hint, font_context);
-            button.position = Point::new(
-                WINDOW_CONTROLS_WIDTH * (number_of_buttons - 1 - idx) as f64
-                    / number_of_buttons as f64,
-                0.,
-            );
+            button.size = button.layout(button_size_hint, font_context, palette);
+            button.position = Point::new(BUTTON_WIDTH * idx as f64, 0.);
         }
-        Size::new(WINDOW_CONTROLS_WIDTH, WINDOW_CONTROLS_HEIGHT)
+        Size::new(self.width(), WINDOW_CONTROLS_HEIGHT)
     }
-    fn paint(&mut self, scene: &mut vello::Scene) {
+    fn paint(&mut self, scene: &mut vello::Scene, palette: &Palette) {
         for child in self.children_mut() {
-            child.paint(scene);
+            child.paint(scene, palette);
         }
     }
     fn children(&self) -> Vec<&super::WidgetData<State>> {
@@ -120,3 +150,65 @@ impl<State: 'static> Widget<State> for WindowControls<State> {
         event::Status::Ignored
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use WindowButton::*;
+
+    #[test]
+    fn button_layout_splits_sides_at_the_colon() {
+        let layout = ButtonLayout::from("close,minimize:maximize");
+        assert_eq!(layout.start, vec![Close, Minimize]);
+        assert_eq!(layout.end, vec![Maximize]);
+    }
+
+    #[test]
+    fn button_layout_without_colon_puts_everything_at_the_start() {
+        let layout = ButtonLayout::from("minimize,close");
+        assert_eq!(layout.start, vec![Minimize, Close]);
+        assert!(layout.end.is_empty());
+    }
+
+    #[test]
+    fn button_layout_empty_sides() {
+        assert_eq!(
+            ButtonLayout::from(":"),
+            ButtonLayout {
+                start: vec![],
+                end: vec![]
+            }
+        );
+        assert_eq!(
+            ButtonLayout::from(""),
+            ButtonLayout {
+                start: vec![],
+                end: vec![]
+            }
+        );
+        let layout = ButtonLayout::from("close:");
+        assert_eq!(layout.start, vec![Close]);
+        assert!(layout.end.is_empty());
+    }
+
+    #[test]
+    fn button_layout_skips_unknown_names() {
+        let layout = ButtonLayout::from("appmenu,close:spacer,minimize,icon,,maximize");
+        assert_eq!(layout.start, vec![Close]);
+        assert_eq!(layout.end, vec![Minimize, Maximize]);
+    }
+
+    #[test]
+    fn button_layout_trims_whitespace() {
+        let layout = ButtonLayout::from(" close , minimize :\tmaximize ");
+        assert_eq!(layout.start, vec![Close, Minimize]);
+        assert_eq!(layout.end, vec![Maximize]);
+    }
+
+    #[test]
+    fn button_layout_default_matches_gnome() {
+        let layout = ButtonLayout::default();
+        assert!(layout.start.is_empty());
+        assert_eq!(layout.end, vec![Minimize, Maximize, Close]);
+    }
+}
@@ -1,16 +1,22 @@
 use std::sync::Arc;
 
 use crate::app::InternalMessage;
-use crate::event::WidgetEvent;
+use crate::clipboard::Clipboard;
+use crate::event::keyboard::{Key, NamedKey};
+use crate::event::mouse::MouseButton;
 use crate::event::{self, EventContext};
+use crate::event::{keyboard, WidgetEvent};
 use crate::renderer::RenderEngine;
 use crate::view::{RootView, View};
-use crate::widget::{RootWidget, Widget, WidgetIdPath};
+use crate::widget::{self, Decorations, RootWidget, Widget, WidgetIdPath, WindowSettings};
+use crate::{Palette, Task};
 use parley::FontContext;
 use vello::peniko::kurbo::{Point, Rect, Size};
-use winit::dpi::PhysicalSize;
+use winit::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
 use winit::event_loop::ActiveEventLoop;
-use winit::window::{Window as WinitWindow, WindowId};
+use winit::window::{
+    CursorIcon, Fullscreen, Icon, Window as WinitWindow, WindowAttributes, WindowId, WindowLevel,
+};
 pub struct Window<State: 'static, V: View<State>> {
     // id: String,
     winit_window: Arc<WinitWindow>,
@@ -21,7 +27,13 @@ pub struct Window<State: 'static, V: View<State>> {
     pub root_widget: RootWidget<State>,
     pub root_view: RootView<State, V>,
     cursor_pos: Point,
+    modifiers: keyboard::Modifiers,
     hovered_widget: WidgetIdPath,
+    focused_widget: Option<WidgetIdPath>,
+    ime_cursor_area: Option<Rect>,
+    settings: WindowSettings,
+    /// The palette of the app theme, set again after every state change
+    pub palette: Palette,
     bounds_tree: Vec<(WidgetIdPath, Rect)>,
     render_engine: RenderEngine,
 }
@@ -35,29 +47,38 @@ where
         event_loop: &ActiveEventLoop,
         root_view: RootView<State, V>,
         _id: String,
+        palette: Palette,
+        clipboard: Clipboard,
     ) -> Self {
-        let window_attributes = WinitWindow::default_attributes()
-            .with_decorations(false)
-            .with_transparent(true)
-            .with_min_inner_size(winit::dpi::LogicalSize::new(200, 200));
+        let mut root_widget = root_view.build_widget();
+        let root_child_id = root_widget.child().id;
+        let settings = window_settings(&mut root_widget);
+        let mut window_attributes = window_attributes(&settings);
+        // shown in the title bar with server side decorations
+        if let Some(window) = window_widget(&mut root_widget) {
+            window_attributes = window_attributes.with_title(window.title.clone());
+        }
+
         let window = Arc::new(event_loop.create_window(window_attributes).unwrap());
         let physical_size = window.inner_size();
         let logical_size = (physical_size.width as f64, physical_size.height as f64).into();
-
-        let mut root_widget = root_view.build_widget();
-        let root_child_id = root_widget.child().id;
         Self {
             // id,
             winit_window: window.clone(),
             physical_size,
             logical_size,
             scale_factor: 1.0,
-            event_context: EventContext::new(window.clone()),
+            event_context: EventContext::new(window.clone(), clipboard),
             root_widget,
             root_view,
             cursor_pos: Point::ZERO,
+            modifiers: keyboard::Modifiers::empty(),
             // FIXME: This shouldn't be necessary
             hovered_widget: vec![root_child_id],
+            focused_widget: None,
+            ime_cursor_area: None,
+            settings,
+            palette,
             bounds_tree: vec![],
             render_engine: RenderEngine::new(
                 window.clone(),
@@ -88,6 +109,10 @@ where
         self.cursor_pos = cursor_pos
     }
 
+    pub fn modifiers(&self) -> keyboard::Modifiers {
+        self.modifiers
+    }
+
     pub fn request_redraw(&self) {
         self.winit_window.request_redraw();
     }
@@ -98,6 +123,7 @@ where
             self.physical_size.width as f64 / self.scale_factor,
             self.physical_size.height as f64 / self.scale_factor,
         );
+        self.update_window_state();
         self.layout(font_context);
         self.render_engine
             .resize(self.physical_size.width, self.physical_size.height);
@@ -106,13 +132,13 @@ where
 
     pub fn layout(&mut self, font_context: &mut FontContext) {
         self.root_widget
-            .layout(self.logical_size.into(), font_context);
+            .layout(self.logical_size.into(), font_context, &self.palette);
         self.bounds_tree = self.root_widget.bounds_tree(Vec::new(), Point::ZERO);
     }
 
     pub fn paint(&mut self) {
         let mut scene = vello::Scene::new();
-        self.root_widget.paint(&mut scene);
+        self.root_widget.paint(&mut scene, &self.palette);
         // draw debug layout thing
         use rand::{Rng, SeedableRng};
         use vello::kurbo::Affine;
@@ -131,25 +157,178 @@ where
         let mut scaled_scene = vello::Scene::new();
         scaled_scene.append(&scene, Some(Affine::scale(self.scale_factor)));
         self.render_engine.render(&scaled_scene);
+        self.update_ime();
+    }
+
+    /// Hands the state of the winit window that changes the chrome over to the window widget.
+    /// Compositors that only support server side decorations draw them even when they
+    /// weren't asked for, the window widget has to leave out its own then. Only checked
+    /// on resize, winit reports the window as decorated until it is configured, and every
+    /// change of these states resizes the window anyway
+    fn update_window_state(&mut self) {
+        let server_decorated = self.winit_window.is_decorated();
+        let maximized = self.winit_window.is_maximized();
+        let fullscreen = self.winit_window.fullscreen().is_some();
+        if let Some(window) = window_widget(&mut self.root_widget) {
+            if window.server_decorated != server_decorated {
+                tracing::trace!("Server side decorations: {server_decorated}");
+                window.server_decorated = server_decorated;
+            }
+            window.maximized = maximized;
+            window.fullscreen = fullscreen;
+        }
+    }
+
+    /// Enables IME while the focused widget takes text input and keeps
+    /// the candidate window next to its caret
+    fn update_ime(&mut self) {
+        let ime_cursor_area = self
+            .focused_widget
+            .clone()
+            .and_then(|focused_widget| self.root_widget.ime_cursor_area(focused_widget));
+        if ime_cursor_area == self.ime_cursor_area {
+            return;
+        }
+        if ime_cursor_area.is_some() != self.ime_cursor_area.is_some() {
+            self.winit_window.set_ime_allowed(ime_cursor_area.is_some());
+        }
+        if let Some(area) = ime_cursor_area {
+            self.winit_window.set_ime_cursor_area(
+                LogicalPosition::new(area.x0, area.y0),
+                LogicalSize::new(area.width(), area.height()),
+            );
+        }
+        self.ime_cursor_area = ime_cursor_area;
     }
 
     pub fn reconciliate(&mut self, view: V) {
         let new = RootView::new(view);
         new.reconciliate(&self.root_view, &mut self.root_widget);
         self.root_view = new;
+        let settings = window_settings(&mut self.root_widget);
+        if settings != self.settings {
+            self.apply_settings(settings);
+        }
+        if let Some(focused_widget) = &self.focused_widget {
+            if !self.root_widget.focus_chain().contains(focused_widget) {
+                self.focused_widget = None;
+            }
+        }
+    }
+
+    /// Applies the settings that changed since the last reconciliation to the open window
+    fn apply_settings(&mut self, settings: WindowSettings) {
+        let old = &self.settings;
+        let window = &self.winit_window;
+        if settings.inner_size != old.inner_size {
+            if let Some(size) = settings.inner_size {
+                _ = window.request_inner_size(LogicalSize::new(size.width, size.height));
+            }
+        }
+        if settings.min_inner_size != old.min_inner_size {
+            window.set_min_inner_size(
+                settings
+                    .min_inner_size
+                    .map(|size| LogicalSize::new(size.width, size.height)),
+            );
+        }
+        if settings.max_inner_size != old.max_inner_size {
+            window.set_max_inner_size(
+                settings
+                    .max_inner_size
+                    .map(|size| LogicalSize::new(size.width, size.height)),
+            );
+        }
+        if settings.position != old.position {
+            if let Some(position) = settings.position {
+                window.set_outer_position(LogicalPosition::new(position.x, position.y));
+            }
+        }
+        if settings.resizable != old.resizable {
+            window.set_resizable(settings.resizable);
+        }
+        if settings.always_on_top != old.always_on_top {
+            window.set_window_level(window_level(settings.always_on_top));
+        }
+        if settings.fullscreen != old.fullscreen {
+            window.set_fullscreen(settings.fullscreen.then_some(Fullscreen::Borderless(None)));
+        }
+        if settings.maximized != old.maximized {
+            window.set_maximized(settings.maximized);
+        }
+        if settings.decorations != old.decorations {
+            window.set_decorations(settings.decorations == Decorations::ServerSide);
+        }
+        if settings.transparent != old.transparent {
+            window.set_transparent(settings.transparent);
+        }
+        if settings.icon != old.icon {
+            window.set_window_icon(settings.icon.as_ref().and_then(window_icon));
+        }
+        if settings.app_id != old.app_id {
+            tracing::warn!("The app id can't be changed after the window is opened");
+        }
+        self.settings = settings;
+    }
+
+    /// Lets the `on_close` handler of the window view decide what happens,
+    /// returns `None` if there is no handler
+    pub fn on_close_requested(&mut self, state: &mut State) -> Option<Task<State>> {
+        let window = window_widget(&mut self.root_widget)?;
+        let on_close = window.on_close.clone()?;
+        Some(on_close(state))
+    }
+
+    pub fn set_focus(&mut self, id_path: Option<WidgetIdPath>) {
+        if self.focused_widget == id_path {
+            return;
+        }
+        if let Some(previous) = self.focused_widget.take() {
+            self.root_widget.send_focus(false, previous);
+        }
+        if let Some(id_path) = &id_path {
+            self.root_widget.send_focus(true, id_path.clone());
+        }
+        self.focused_widget = id_path;
+        self.winit_window.request_redraw();
+    }
+
+    /// Moves the focus to the next (or previous) focusable widget in tree order
+    fn traverse_focus(&mut self, backwards: bool) {
+        let focus_chain = self.root_widget.focus_chain();
+        if focus_chain.is_empty() {
+            return;
+        }
+        let current = self
+            .focused_widget
+            .as_ref()
+            .and_then(|focused| focus_chain.iter().position(|id_path| id_path == focused));
+        let next = match (current, backwards) {
+            (None, false) => 0,
+            (None, true) => focus_chain.len() - 1,
+            (Some(idx), false) => (idx + 1) % focus_chain.len(),
+            (Some(idx), true) => (idx + focus_chain.len() - 1) % focus_chain.len(),
+        };
+        self.set_focus(Some(focus_chain[next].clone()));
     }
 
     pub fn widget_event(
         &mut self,
         event: WidgetEvent,
         state: &mut State,
+        font_context: &mut FontContext,
         should_close: &mut bool,
         state_changed: &mut bool,
-    ) {
+    ) -> Task<State> {
         // eprintln!("{:#?}", self.root_widget.child());
 
         if let WidgetEvent::Mouse(event::mouse::Event::Move { position, .. }) = event.clone() {
             self.set_cursor_pos(position);
+            // widgets set the cursor again for every move they are hovered by
+            self.event_context.set_cursor(CursorIcon::Default);
+        }
+        if let WidgetEvent::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            self.modifiers = modifiers;
         }
         let previous = self.hovered_widget.clone();
         let _: Vec<()> = self
@@ -167,31 +346,198 @@ where
                 .send_hover(true, self.hovered_widget.clone());
             self.winit_window.request_redraw();
         }
-        self.root_widget.send_event(
-            event,
-            &mut self.event_context,
-            self.hovered_widget.clone(),
-            state,
-        );
+        let target = match (&event, &self.focused_widget) {
+            (WidgetEvent::Keyboard(_) | WidgetEvent::Ime(_), Some(focused_widget)) => {
+                focused_widget.clone()
+            }
+            (WidgetEvent::Keyboard(_) | WidgetEvent::Ime(_), None) => {
+                vec![self.root_widget.child().id]
+            }
+            _ => self.hovered_widget.clone(),
+        };
+        let status =
+            self.root_widget
+                .send_event(event.clone(), &mut self.event_context, target, state);
+        if let Some(id_path) = self.event_context.take_requested_focus() {
+            self.set_focus(Some(id_path));
+        } else if status == event::Status::Ignored {
+            if let WidgetEvent::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(NamedKey::Tab),
+                modifiers,
+                ..
+            }) = event
+            {
+                self.traverse_focus(modifiers.shift());
+            }
+        }
         self.winit_window.set_cursor(self.event_context.cursor());
 
         if self.event_context.repaint_needed {
             self.winit_window.request_redraw();
             self.event_context.repaint_needed = false;
         }
-        for message in self.event_context.drain_internal_messages() {
-            match message {
-                InternalMessage::DragResizeWindow(_) => {}
-                InternalMessage::DragMoveWindow => {}
-                InternalMessage::MinimiseWindow => self.winit_window.set_minimized(true),
-                InternalMessage::MaximiseWindow => self
-                    .winit_window
-                    .set_maximized(!self.winit_window.is_maximized()),
-                InternalMessage::CloseWindow => *should_close = true,
-                InternalMessage::TitleChanged(title) => self.winit_window.set_title(title.as_str()),
-            }
+        let captured = status == event::Status::Captured;
+        let messages: Vec<_> = self.event_context.drain_internal_messages().collect();
+        for message in messages {
+            self.handle_internal_message(message, captured, should_close);
         }
         *state_changed = self.event_context.state_changed;
         self.event_context.state_changed = false;
+        // a state change lays out the window again anyway
+        if self.event_context.layout_needed && !*state_changed {
+            self.layout(font_context);
+            self.winit_window.request_redraw();
+        }
+        self.event_context.layout_needed = false;
+        self.event_context.take_tasks()
+    }
+
+    fn handle_internal_message(
+        &mut self,
+        message: InternalMessage,
+        captured: bool,
+        should_close: &mut bool,
+    ) {
+        match message {
+            InternalMessage::DragResizeWindow(_) => {}
+            InternalMessage::DragMoveWindow => {
+                let _ = self.winit_window.drag_window();
+            }
+            InternalMessage::MinimiseWindow => self.winit_window.set_minimized(true),
+            InternalMessage::MaximiseWindow => self
+                .winit_window
+                .set_maximized(!self.winit_window.is_maximized()),
+            InternalMessage::CloseWindow => *should_close = true,
+            // winit only supports the window menu on Windows for now
+            InternalMessage::ShowWindowMenu => self
+                .winit_window
+                .show_window_menu(LogicalPosition::new(self.cursor_pos.x, self.cursor_pos.y)),
+            // TODO: Lower the window, winit 0.30 has no way to do that
+            InternalMessage::LowerWindow => {}
+            InternalMessage::TitleChanged(title) => self.winit_window.set_title(title.as_str()),
+            // a press on empty space of the header stands for one of the messages above
+            InternalMessage::HeaderPressed {
+                button,
+                double_click,
+            } if !captured => {
+                if let Some(message) = header_press_message(button, double_click) {
+                    self.handle_internal_message(message, captured, should_close);
+                }
+            }
+            // captured by a widget inside the header
+            InternalMessage::HeaderPressed { .. } => {}
+        }
+    }
+}
+
+/// Same as libadwaita: a press moves the window, double-click toggles maximize,
+/// middle-click lowers the window and right-click opens the window menu
+fn header_press_message(button: MouseButton, double_click: bool) -> Option<InternalMessage> {
+    match button {
+        MouseButton::Left if double_click => Some(InternalMessage::MaximiseWindow),
+        MouseButton::Left => Some(InternalMessage::DragMoveWindow),
+        MouseButton::Middle => Some(InternalMessage::LowerWindow),
+        MouseButton::Right => Some(InternalMessage::ShowWindowMenu),
+        _ => None,
+    }
+}
+
+/// The settings of the window view at the root, or the defaults if the root is something else
+fn window_settings<State: 'static>(root_widget: &mut RootWidget<State>) -> WindowSettings {
+    window_widget(root_widget)
+        .map(|window| window.settings.clone())
+        .unwrap_or_default()
+}
+
+fn window_widget<State: 'static>(
+    root_widget: &mut RootWidget<State>,
+) -> Option<&mut widget::Window<State>> {
+    let child = &mut *root_widget.child().inner;
+    // type erased views box their widget once more
+    if (*child).as_any().is::<Box<dyn Widget<State>>>() {
+        let boxed = (*child)
+            .as_any_mut()
+            .downcast_mut::<Box<dyn Widget<State>>>()
+            .unwrap();
+        return (**boxed)
+            .as_any_mut()
+            .downcast_mut::<widget::Window<State>>();
+    }
+    (*child)
+        .as_any_mut()
+        .downcast_mut::<widget::Window<State>>()
+}
+
+fn window_attributes(settings: &WindowSettings) -> WindowAttributes {
+    let mut attributes = WinitWindow::default_attributes()
+        .with_resizable(settings.resizable)
+        .with_window_level(window_level(settings.always_on_top))
+        .with_fullscreen(settings.fullscreen.then_some(Fullscreen::Borderless(None)))
+        .with_maximized(settings.maximized)
+        .with_decorations(settings.decorations == Decorations::ServerSide)
+        .with_transparent(settings.transparent)
+        .with_window_icon(settings.icon.as_ref().and_then(window_icon));
+    if let Some(size) = settings.inner_size {
+        attributes = attributes.with_inner_size(LogicalSize::new(size.width, size.height));
+    }
+    if let Some(size) = settings.min_inner_size {
+        attributes = attributes.with_min_inner_size(LogicalSize::new(size.width, size.height));
+    }
+    if let Some(size) = settings.max_inner_size {
+        attributes = attributes.with_max_inner_size(LogicalSize::new(size.width, size.height));
+    }
+    if let Some(position) = settings.position {
+        attributes = attributes.with_position(LogicalPosition::new(position.x, position.y));
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(app_id) = &settings.app_id {
+        use winit::platform::wayland::WindowAttributesExtWayland;
+        attributes = attributes.with_name(app_id, "");
+    }
+    attributes
+}
+
+fn window_level(always_on_top: bool) -> WindowLevel {
+    if always_on_top {
+        WindowLevel::AlwaysOnTop
+    } else {
+        WindowLevel::Normal
+    }
+}
+
+fn window_icon(icon: &widget::WindowIcon) -> Option<Icon> {
+    Icon::from_rgba(icon.rgba.clone(), icon.width, icon.height)
+        .inspect_err(|err| tracing::warn!("Invalid window icon: {err}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_presses_map_to_window_messages() {
+        assert!(matches!(
+            header_press_message(MouseButton::Left, false),
+            Some(InternalMessage::DragMoveWindow)
+        ));
+        assert!(matches!(
+            header_press_message(MouseButton::Left, true),
+            Some(InternalMessage::MaximiseWindow)
+        ));
+        assert!(matches!(
+            header_press_message(MouseButton::Middle, false),
+            Some(InternalMessage::LowerWindow)
+        ));
+        assert!(matches!(
+            header_press_message(MouseButton::Right, false),
+            Some(InternalMessage::ShowWindowMenu)
+        ));
+    }
+
+    #[test]
+    fn other_header_presses_do_nothing() {
+        assert!(header_press_message(MouseButton::Back, false).is_none());
+        assert!(header_press_message(MouseButton::Forward, true).is_none());
     }
 }